@@ -1,163 +1,67 @@
-use std::collections::HashMap;
+use core::console::Console;
+use core::rng::Rng;
 use world::World;
-use world::room::{Direction, Exit, Room, GoBack, make_exit};
-use world::actions::{Action, OnAction};
-use world::conversation::{Converse, Choices};
-use world::person::Person;
-use character::charactersheet::{Character, create_stats, select_class};
+use world::loader::{load_world_from_str, Format, LoadError};
+use character::charactersheet::{Character, create_stats, select_class, select_race};
 use game::Game;
-use core::stat_bonus::create_all_stat_bonuses;
+
+/// The Dark Harvest adventure file.
+///
+/// The file is built into the game, so it can be played from where ever the game is installed.
+pub const DARK_HARVEST: &str = include_str!("../../data/dark_harvest.toml");
+
+/// Build the Dark Harvest world from its adventure file.
+pub fn dark_harvest_world() -> Result<World, LoadError> {
+    return load_world_from_str(DARK_HARVEST, Format::Toml, "dark_harvest.toml");
+}
 
 /// This is the actual game adventure It's self.
 ///
 /// This adventure will start with you creating your character before describing the Dark Harvest
 /// world. Finally we create the game object and run the game.
-pub fn run_dark_harvest(mut character: Character) {
+///
+/// The game is played through the console, so pass a Terminal to play it for real. Every roll,
+/// from the characters stats onwards, comes from the rng.
+pub fn run_dark_harvest(character: Character, mut console: Box<dyn Console>, mut rng: Rng) {
+
+    let world = match dark_harvest_world() {
+        Ok(world) => world,
+        Err(e) => {
+            console.println(&format!("Dark Harvest could not be loaded: {}", e));
+            return;
+        }
+    };
 
-    println!("\nCharacter Creation: Help us create your character sheet.");
+    console.println("\nCharacter Creation: Help us create your character sheet.");
 
-    character = create_stats(select_class(character));
+    // The player quit while making their character.
+    let character = match select_race(character, console.as_mut())
+        .and_then(|character| select_class(character, console.as_mut()))
+        .and_then(|character| create_stats(character, &world.stat_methods, console.as_mut(), &mut rng)) {
+        Some(character) => character,
+        None => return,
+    };
 
-    let world = dark_harvest_intro();
+    dark_harvest_intro(console.as_mut());
 
-    let mut game = Game {
-        active: true,
-        game_character: character,
-        stat_bonuses: create_all_stat_bonuses(),
-        current_room: None,
-        previous_room: None,
-    };
+    let mut game = Game::new(character, world, console).with_rng(rng);
 
-    game.run(world);
+    game.run();
 }
 
-fn dark_harvest_intro() -> World {
-    println!("==== [Dark Harvest] ===");
-    println!("\nIntroduction:");
-    println!("
+fn dark_harvest_intro(console: &mut dyn Console) {
+    console.println("==== [Dark Harvest] ===");
+    console.println("\nIntroduction:");
+    console.println("
     Welcome to Dark Harvest. This is the first chapter in the story of: The Child and The Poet, a dark story
     revolving around love, loss and other worlds. While inspired by DND, Dark Harvest doesn't follow any rule books
     closely. Only as a reference.
     ");
-    println!("Story:");
-    println!("\nYou awaken in the middle of a field. Covered in blood, you have no idea how you got here. As you stand and adjust your eyes to the blinding sun, you feel your body for wounds, to find the source of the bleeding. No wounds present them selves.");
-    println!(r#"The wind kicks up and a voice is heard, like a whisper moving through the shadows, "Who are you?""#);
-    println!("\n\nAdventure Difficulty: Easy");
-    println!("Adventure Length:      Medium");
-    println!("=======================");
-
-    let world = World::new(make_starting_room());
-
-    println!("\n");
-    println!("Location: {}", world.get_base_room().name());
-    println!("\n");
-    println!("{}", world.get_base_room().describe());
-    println!("\n");
-    println!("What do you do? (type help for commands)");
-
-    return world;
+    console.println("Story:");
+    console.println("\nYou awaken in the middle of a field. Covered in blood, you have no idea how you got here. As you stand and adjust your eyes to the blinding sun, you feel your body for wounds, to find the source of the bleeding. No wounds present them selves.");
+    console.println(r#"The wind kicks up and a voice is heard, like a whisper moving through the shadows, "Who are you?""#);
+    console.println("\n\nAdventure Difficulty: Easy");
+    console.println("Adventure Length:      Medium");
+    console.println("=======================");
 }
 
-
-fn make_starting_room() -> Room {
-    let mut actions = HashMap::new();
-
-    actions.insert(Action::Explore, Some(OnAction::new("You look around and see nothing of interest. Exploring to the South and West show more of the ruins. They look like modern day buildings, or the left overs after nature scavanged them. After man abandonded them.".to_string(), None)));
-
-    let mut exits = Vec::new();
-
-    exits.push(make_exit(Direction::N, make_path_way()));
-
-    return Room::new(
-        "Grassy Clearing".to_string(),
-        "The sound of birds, the rustling of the wind. The warmth of the sun in the blue sky. The grassy clearing is clear, trees to the east, a small path to the north. South an West contain ruins and broken peices of what appears to be concrete".to_string(),
-        actions,
-        exits,
-        GoBack::new(true, None),
-        None
-    );
-}
-
-fn make_path_way() -> Room {
-    let mut actions = HashMap::new();
-
-    actions.insert(Action::Explore, Some(OnAction::new("You look around the path, over at the trees and up at the sky. You explore your surroundings and feel like someone is watching you. Perhaps their up ahead, or behind you, or maybe you're just going crazy.".to_string(), None)));
-
-    let mut exits = Vec::new();
-
-    exits.push(make_exit(Direction::N, make_creek()));
-
-    return Room::new(
-        "Path".to_string(),
-        "As you walk up the path the trees around seem to get thicker, taller and the area darker with shade. The path continues to go north.".to_string(),
-        actions,
-        exits,
-        GoBack::new(true, None),
-        None
-    );
-}
-
-fn make_creek() -> Room {
-    let mut actions = HashMap::new();
-
-    actions.insert(Action::Talk, Some(OnAction::new("You enter the conversation.".to_string(), None)));
-
-    let mut exits = Vec::new();
-
-    exits.push(Exit {
-        direction: Direction::NONE,
-        room: None
-    });
-
-    let people = vec![make_poet_by_river()];
-
-
-    return Room::new(
-        "River".to_string(),
-        r#"Continuing to follow the path, you come across a creek. The water is softly and quietly moving along its course. A prescence causes you to shudder and turn around. You see an old man standing behind you wearing a fedora red robes leaning on a staff. He looks at you for a moment before saying: "Hello there!""#.to_string(),
-        actions,
-        exits,
-        GoBack::new(false, Some("There is something preventing you from going back. Is there something to do here?".to_string())),
-        Some(people)
-    );
-}
-
-fn make_answer(nested_answer: Vec<Choices>) -> Vec<Choices> {
-    let mut answer = Vec::new();
-
-    answer.push(
-        Choices::new(
-            "Who are you?".to_string(),
-            Converse::new(
-                "Who am I? I am the Poet. Who are you child? Dont speak your name, it sits on my toungue. Where are you from?".to_string(),
-                Some(nested_answer),
-            )
-        )
-    );
-
-    return answer;
-}
-
-fn make_nested_answer() -> Vec<Choices> {
-    let mut second_answer = Vec::new();
-
-    second_answer.push(
-        Choices::new(
-            "Not from here ...".to_string(),
-            Converse::new(
-                "I can see that. We should get you inside before it rains. Follow me. Its a short distance to my house. Come along your safe.".to_string(),
-                None,
-            )
-        )
-    );
-
-    return second_answer;
-}
-
-fn make_poet_by_river() -> Person {
-    return Person::new(
-        "Mysterious Old Man".to_string(),
-        None,
-        Converse::new(r#"The old man looks at you and asks: "Are you ok? Are you lost?""#.to_string(), Some(make_answer(make_nested_answer()))),
-    );
-}
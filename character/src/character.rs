@@ -1 +1,2 @@
 pub mod charactersheet;
+pub mod stat_methods;
@@ -1,26 +1,114 @@
 use std::option::Option;
-use std::io;
-use std::io::Write;
-use std::process;
-use rand::Rng;
+use core::console::Console;
+use core::rng::Rng;
 use core::text_handeling::unwrap_str;
+use core::stats::Stat;
+use world::item::{Item, find_item};
+use crate::character::stat_methods::{StatMethod, choose_method, generate};
 
 use prettytable::{Table};
+use serde::{Serialize, Deserialize};
 
 /// Defeinition of a character in the game.
 ///
 /// All characters must have a name, other options can be
 /// optional.
-#[derive(Clone, Debug)]
+///
+/// The inventory is every item the character is carrying.
+///
+/// Hit points start at the max hp of the characters class, once they pick one. When they
+/// reach 0 the character is defeated.
+///
+/// Characters start at level 1 with no xp. bonus_hp is the max hp they have gained from
+/// leveling up, on top of what their class gives them.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Character {
     pub name: String,
     pub stats: Option<Stats>,
     pub class: Option<Class>,
     pub race: Option<Race>,
+    #[serde(default)]
+    pub inventory: Vec<Item>,
+    #[serde(default)]
+    pub hp: i32,
+    #[serde(default = "default_level")]
+    pub level: i32,
+    #[serde(default)]
+    pub xp: i32,
+    #[serde(default)]
+    pub bonus_hp: i32,
+}
+
+/// Implementation of character.
+impl Character {
+
+    /// Find an item the character is carrying by name or alias.
+    pub fn find_item(&self, name: &str) -> Option<&Item> {
+        return find_item(&self.inventory, name).map(|index| &self.inventory[index]);
+    }
+
+    /// Is the character carrying the item?
+    pub fn has_item(&self, name: &str) -> bool {
+        return self.find_item(name).is_some();
+    }
+
+    /// Add an item to the inventory.
+    pub fn add_item(&mut self, item: Item) {
+        self.inventory.push(item);
+    }
+
+    /// Take an item out of the inventory.
+    pub fn remove_item(&mut self, name: &str) -> Option<Item> {
+        return find_item(&self.inventory, name).map(|index| self.inventory.remove(index));
+    }
+
+    /// The total weight of everything the character is carrying.
+    pub fn carried_weight(&self) -> i32 {
+        return self.inventory.iter().map(|item| item.weight).sum();
+    }
+
+    /// The most hit points the character can have, from their class and their levels.
+    pub fn max_hp(&self) -> i32 {
+        return self.class.as_ref().map_or(0, |class| class.max_hp) + self.bonus_hp;
+    }
+
+    /// Take hit points from the character, but not below 0.
+    pub fn damage(&mut self, amount: i32) {
+        self.hp = (self.hp - amount.max(0)).max(0);
+    }
+
+    /// Give hit points back to the character, up to their max.
+    pub fn heal(&mut self, amount: i32) {
+        self.hp = (self.hp + amount.max(0)).min(self.max_hp());
+    }
+
+    /// Has the character run out of hit points?
+    pub fn is_defeated(&self) -> bool {
+        return self.hp <= 0;
+    }
+
+    /// Does the characters race have the trait with this name, for example: darkvision?
+    pub fn has_trait(&self, name: &str) -> bool {
+        return match &self.race {
+            Some(race) => race.traits.iter().any(|race_trait| race_trait.name() == name.to_lowercase()),
+            None => false,
+        };
+    }
+
+    /// The bonus the characters race adds to rolls made with the stat.
+    pub fn skill_bonus(&self, stat: Stat) -> i32 {
+        return match &self.race {
+            Some(race) => race.traits.iter().map(|race_trait| match race_trait {
+                Trait::SkillBonus { stat: bonus_stat, bonus } if *bonus_stat == stat => *bonus,
+                _ => 0,
+            }).sum(),
+            None => 0,
+        };
+    }
 }
 
 /// Stats of a character.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Stats {
     pub int: i32,
     pub str: i32,
@@ -40,44 +128,206 @@ impl Stats {
             dur: dur,
         }
     }
+
+    /// Get the value of a stat.
+    pub fn get(&self, stat: Stat) -> i32 {
+        match stat {
+            Stat::Str => self.str,
+            Stat::Int => self.int,
+            Stat::Dex => self.dex,
+            Stat::Chr => self.chr,
+            Stat::Dur => self.dur,
+        }
+    }
+
+    /// Set the value of a stat.
+    pub fn set(&mut self, stat: Stat, value: i32) {
+        match stat {
+            Stat::Str => self.str = value,
+            Stat::Int => self.int = value,
+            Stat::Dex => self.dex = value,
+            Stat::Chr => self.chr = value,
+            Stat::Dur => self.dur = value,
+        }
+    }
+
+    /// Every stat and its value, in the order they are shown to the player.
+    pub fn summary(&self) -> String {
+        let stats: Vec<String> = Stat::all().iter().map(|stat| format!("{}: {}", stat.name(), self.get(*stat))).collect();
+
+        return stats.join(", ");
+    }
 }
 
 /// Character race.
-#[derive(Clone, Debug)]
+///
+/// A race changes the characters stats when they are made, see: apply, and gives them
+/// traits that the game checks as the player goes, such as darkvision.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Race {
     pub name: String,
+    #[serde(default)]
+    pub modifiers: Vec<StatModifier>,
+    #[serde(default)]
+    pub traits: Vec<Trait>,
 }
 
 /// Implementation of race.
 impl Race {
     pub fn new(name: String) -> Self {
         Race {
-            name: name
+            name: name,
+            modifiers: Vec::new(),
+            traits: Vec::new(),
+        }
+    }
+
+    /// Add the amount to the stat when the characters stats are made.
+    pub fn with_modifier(mut self, stat: Stat, amount: i32) -> Self {
+        self.modifiers.push(StatModifier { stat: stat, amount: amount });
+
+        return self;
+    }
+
+    /// Give the race a trait.
+    pub fn with_trait(mut self, race_trait: Trait) -> Self {
+        self.traits.push(race_trait);
+
+        return self;
+    }
+
+    /// Apply the modifiers to the stats.
+    ///
+    /// Stats stay between 1 and the highest stat there is a bonus for.
+    pub fn apply(&self, stats: &mut Stats) {
+        for modifier in &self.modifiers {
+            let value = stats.get(modifier.stat) + modifier.amount;
+
+            stats.set(modifier.stat, value.max(1).min(MAX_STAT));
+        }
+    }
+
+    /// The modifiers as shown to the player, for example: +2 dex, -1 chr.
+    pub fn modifier_summary(&self) -> String {
+        let modifiers: Vec<String> = self.modifiers.iter().map(|modifier| format!("{:+} {}", modifier.amount, modifier.stat.name())).collect();
+
+        return modifiers.join(", ");
+    }
+
+    /// The traits as shown to the player.
+    pub fn trait_summary(&self) -> String {
+        if self.traits.is_empty() {
+            return "none".to_string();
+        }
+
+        let traits: Vec<String> = self.traits.iter().map(|race_trait| race_trait.describe()).collect();
+
+        return traits.join(", ");
+    }
+}
+
+/// The most a stat can be, races can push a stat past what can be rolled.
+pub const MAX_STAT: i32 = 20;
+
+/// An amount a race adds to, or takes from, a stat.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StatModifier {
+    #[serde(with = "stat_name")]
+    pub stat: Stat,
+    pub amount: i32,
+}
+
+/// Something a race can do that others can't.
+///
+/// - Darkvision: can see in rooms that are dark.
+/// - SkillBonus: adds the bonus to every roll made with the stat, such as looking around
+///   with int or talking with chr.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Trait {
+    Darkvision,
+    SkillBonus {
+        #[serde(with = "stat_name")]
+        stat: Stat,
+        bonus: i32,
+    },
+}
+
+impl Trait {
+
+    /// The name adventures use to check for the trait, for example: { trait = "darkvision" }.
+    pub fn name(&self) -> String {
+        match self {
+            Trait::Darkvision => "darkvision".to_string(),
+            Trait::SkillBonus { stat, .. } => format!("{} bonus", stat.name()),
+        }
+    }
+
+    /// The trait as shown to the player.
+    pub fn describe(&self) -> String {
+        match self {
+            Trait::Darkvision => "darkvision".to_string(),
+            Trait::SkillBonus { stat, bonus } => format!("{:+} to {} rolls", bonus, stat.name()),
         }
     }
 }
 
+/// Stats are saved by their short name, for example: dex.
+mod stat_name {
+    use core::stats::Stat;
+    use serde::{Deserialize, Deserializer, Serializer};
+    use serde::de::Error;
+
+    pub fn serialize<S: Serializer>(stat: &Stat, serializer: S) -> Result<S::Ok, S::Error> {
+        return serializer.serialize_str(stat.name());
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Stat, D::Error> {
+        let name = String::deserialize(deserializer)?;
+
+        return Stat::from_name(&name).ok_or_else(|| D::Error::custom(format!("unknown stat: {}", name)));
+    }
+}
+
 /// Character class.
-#[derive(Clone, Debug)]
+///
+/// What the class can do decides the class abilities the player can use:
+///
+/// - can_cast: cast SPELL.
+/// - can_steal: steal and pick lock.
+/// - can_force: force and intimidate.
+///
+/// Damage is the dice expression rolled when the character hits something in a fight.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Class {
     pub name: String,
     pub can_cast: bool,
     pub can_steal: bool,
+    #[serde(default)]
+    pub can_force: bool,
     pub max_hp: i32,
+    #[serde(default = "default_damage")]
+    pub damage: String,
 }
 
 /// Implementation of class.
 impl Class {
-    pub fn new(name: String, can_cast: bool, can_steal: bool, max_hp: i32) -> Self {
+    pub fn new(name: String, can_cast: bool, can_steal: bool, can_force: bool, max_hp: i32, damage: String) -> Self {
         Class {
             name: name,
             can_cast: can_cast,
             can_steal: can_steal,
+            can_force: can_force,
             max_hp: max_hp,
+            damage: damage,
         }
     }
 }
 
+/// Classes saved before they had damage hit with a d4.
+fn default_damage() -> String {
+    return "1d4".to_string();
+}
+
 /// Build a character based off name.
 ///
 /// This is great for when we want to get going. All we care
@@ -87,76 +337,181 @@ pub fn build_character(name: String) -> Character {
         name: name,
         race: None,
         class: None,
-        stats: None
+        stats: None,
+        inventory: Vec::new(),
+        hp: 0,
+        level: 1,
+        xp: 0,
+        bonus_hp: 0,
     }
 }
 
-/// Allows the player to select a class.
-///
-/// Classes are pre-defined: Wizard, Thief and Fighter.
-pub fn select_class(mut character: Character) -> Character {
-    let classes: Vec<Class> = vec![
+/// Characters saved before levels were all level 1.
+fn default_level() -> i32 {
+    return 1;
+}
+
+/// The classes a player can pick from: Wizard, Thief and Fighter.
+pub fn classes() -> Vec<Class> {
+    return vec![
         Class::new(
             "Wizard".to_string(),
             true,
             false,
-            4
+            false,
+            4,
+            "1d4".to_string()
         ),
         Class::new(
             "Theif".to_string(),
             false,
             true,
-            6
+            false,
+            6,
+            "1d6".to_string()
         ),
         Class::new(
             "Fighter".to_string(),
             false,
             false,
-            8
+            true,
+            8,
+            "1d8".to_string()
         )
     ];
+}
+
+/// Find a class by its name, ignoring case.
+pub fn find_class(name: &str) -> Option<Class> {
+    return classes().into_iter().find(|class| class.name.to_lowercase() == name.to_lowercase());
+}
+
+/// The races a player can pick from: Human, Elf, Dwarf and Halfling.
+pub fn races() -> Vec<Race> {
+    return vec![
+        Race::new("Human".to_string())
+            .with_modifier(Stat::Str, 1)
+            .with_modifier(Stat::Int, 1)
+            .with_modifier(Stat::Dex, 1)
+            .with_modifier(Stat::Chr, 1)
+            .with_modifier(Stat::Dur, 1),
+        Race::new("Elf".to_string())
+            .with_modifier(Stat::Dex, 2)
+            .with_modifier(Stat::Int, 1)
+            .with_modifier(Stat::Dur, -1)
+            .with_trait(Trait::Darkvision),
+        Race::new("Dwarf".to_string())
+            .with_modifier(Stat::Dur, 2)
+            .with_modifier(Stat::Str, 1)
+            .with_modifier(Stat::Chr, -1)
+            .with_trait(Trait::Darkvision),
+        Race::new("Halfling".to_string())
+            .with_modifier(Stat::Dex, 2)
+            .with_modifier(Stat::Chr, 1)
+            .with_modifier(Stat::Str, -1)
+            .with_trait(Trait::SkillBonus { stat: Stat::Chr, bonus: 2 }),
+    ];
+}
+
+/// Find a race by its name, ignoring case.
+pub fn find_race(name: &str) -> Option<Race> {
+    return races().into_iter().find(|race| race.name.to_lowercase() == name.to_lowercase());
+}
+
+/// Allows the player to select a race.
+///
+/// The player can type the number of the race or its name. The races modifiers are
+/// applied once the stats are made, see: create_stats.
+///
+/// Returns None when the player quits, or the input runs out.
+pub fn select_race(mut character: Character, console: &mut dyn Console) -> Option<Character> {
+    let races: Vec<Race> = races();
 
-    println!("\nHelp us determine your class.");
+    console.println("\nHelp us determine your race.");
 
     let mut table = Table::new();
 
-    table.add_row(row!["Class Name", "Can Cast", "Can Steal", "Max HP"]);
+    table.add_row(row!["#", "Race Name", "Stat Modifiers", "Traits"]);
 
-    for class in &classes {
-        table.add_row(row![class.name, class.can_cast, class.can_steal, class.max_hp]);
+    for (index, race) in races.iter().enumerate() {
+        table.add_row(row![index + 1, race.name, race.modifier_summary(), race.trait_summary()]);
     }
 
-    table.printstd();
+    console.print(&table.to_string());
 
-    println!("Type the Choice Number or quit to exit.");
-    println!("\n");
+    console.println("Type the Choice Number or the Race Name, or quit to exit.");
+    console.println("\n");
 
-    let mut done: bool = false;
-
-    while !done {
-        print!("> ");
+    loop {
+        // Running out of input is the same as typing quit.
+        let input = match console.prompt() {
+            Some(input) => input.trim().to_lowercase(),
+            None => "quit".to_string(),
+        };
 
-        io::stdout().flush().expect("Error flushing stdout!");
+        if input == "quit" {
+            console.println("Bye now!");
+            return None;
+        }
 
-        let mut input = String::new();
+        let chosen = match input.parse::<usize>() {
+            Ok(number) if number >= 1 && number <= races.len() => Some(races[number - 1].clone()),
+            _ => find_race(&input),
+        };
 
-        io::stdin().read_line(&mut input)
-                   .expect("Error reading stdin!");
+        match chosen {
+            Some(race) => {
+                character.race = Some(race);
 
-        if input.ends_with('\n') {
-           input.pop();
+                return Some(character);
+            },
+            None => console.println("race not found"),
         }
+    }
+}
+
+/// Allows the player to select a class.
+///
+/// Classes are pre-defined: Wizard, Thief and Fighter.
+///
+/// Returns None when the player quits, or the input runs out.
+pub fn select_class(mut character: Character, console: &mut dyn Console) -> Option<Character> {
+    let classes: Vec<Class> = classes();
+
+    console.println("\nHelp us determine your class.");
+
+    let mut table = Table::new();
+
+    table.add_row(row!["Class Name", "Can Cast", "Can Steal", "Can Force", "Max HP", "Damage"]);
+
+    for class in &classes {
+        table.add_row(row![class.name, class.can_cast, class.can_steal, class.can_force, class.max_hp, class.damage]);
+    }
+
+    console.print(&table.to_string());
+
+    console.println("Type the Choice Number or quit to exit.");
+    console.println("\n");
+
+    let mut done: bool = false;
+
+    while !done {
+        // Running out of input is the same as typing quit.
+        let input = match console.prompt() {
+            Some(input) => input,
+            None => "quit".to_string(),
+        };
 
         let words: Vec<&str> = input.split_whitespace().collect();
 
         if words.is_empty() {
-            println!("Invalid input. Try again.");
+            console.println("Invalid input. Try again.");
 
         } else if input.to_string() == "quit".to_string() {
-            println!("Bye now!");
-            process::exit(1);
+            console.println("Bye now!");
+            return None;
         } else {
-             let updated_character = parse_input(input, character.clone(), &classes);
+             let updated_character = parse_input(input, character.clone(), &classes, console);
 
              if updated_character.is_some() {
                 character = updated_character.unwrap();
@@ -165,15 +520,15 @@ pub fn select_class(mut character: Character) -> Character {
         }
     }
 
-    return character;
+    return Some(character);
 
 }
 
-fn parse_input(input: String, character: Character, classes: &Vec<Class>) -> Option<Character> {
+fn parse_input(input: String, character: Character, classes: &Vec<Class>, console: &mut dyn Console) -> Option<Character> {
     let words: Vec<&str> = input.split_whitespace().collect();
 
     if words.is_empty() {
-        println!("Invalid input.");
+        console.println("Invalid input.");
     }
 
     let mut result: Vec<String> = Vec::new();
@@ -184,7 +539,7 @@ fn parse_input(input: String, character: Character, classes: &Vec<Class>) -> Opt
         result.push(single_word);
     }
 
-    return parse_commands(result, character, classes);
+    return parse_commands(result, character, classes, console);
 }
 
 fn clean_words(word: String) -> String {
@@ -199,13 +554,13 @@ fn clean_words(word: String) -> String {
     return s;
 }
 
-fn parse_commands(words: Vec<String>, mut character: Character, classes: &Vec<Class>) -> Option<Character> {
+fn parse_commands(words: Vec<String>, mut character: Character, classes: &Vec<Class>, console: &mut dyn Console) -> Option<Character> {
     let mut words = words.iter();
 
     let command = unwrap_str(words.next());
 
     if command == "" {
-        println!("Invalid input.");
+        console.println("Invalid input.");
 
         return None;
     }
@@ -213,18 +568,21 @@ fn parse_commands(words: Vec<String>, mut character: Character, classes: &Vec<Cl
     match command {
         "wizard" => {
             character.class = Some(classes[0].clone());
+            character.hp = classes[0].max_hp;
             return Some(character);
         },
         "fighter" => {
             character.class = Some(classes[2].clone());
+            character.hp = classes[2].max_hp;
             return Some(character);
         },
         "theif" => {
             character.class = Some(classes[1].clone());
+            character.hp = classes[1].max_hp;
             return Some(character);
         },
         _ => {
-            println!("class not found");
+            console.println("class not found");
             return None;
         },
     }
@@ -233,98 +591,28 @@ fn parse_commands(words: Vec<String>, mut character: Character, classes: &Vec<Cl
 
 /// Create the stats for the character and return that character.
 ///
-/// We generate a set of intgers based on the number of stats for the character.
-/// from there we can spit that out and then enter a loop asking you what you wnat to do.
-/// repeat till valid input.
+/// The player picks one of the methods the adventure allows, such as rolling or point buy,
+/// and the stats are made with it. See StatMethod for each of the methods.
 ///
-/// If we encounter re-roll, its the same process all over again with a new set of random numbers.
+/// Anything rolled comes from the rng the game was started with, so the same seed rolls the same stats.
 ///
-/// All stats are considered 3d6. Your lowest possible value is 3 and the highest beign 18.
-pub fn create_stats(character: Character) -> Character {
-    let mut stats: Vec<i32> = Vec::new();
-
-    for _i in 0..5 {
-        stats.push(rand::thread_rng().gen_range(3,18));
-    }
-
-    println!("\nRolled stats:");
-    println!("===============");
-
-    let mut count = 0;
-    let stat_names = vec!["str", "int", "dex", "chr", "dur"];
-
-    for stat in &stats {
-
-        println!("{}: {}", stat_names[count], stat);
-        count = count + 1;
-    }
-
-    println!("===============");
-    println!("What would you like to do? (You can type: accept, re-roll, explain or quit)");
-    println!("\n");
-
-    let mut done = false;
-
-    let mut character = character;
-
-    while !done {
-        print!("> ");
-
-        io::stdout().flush().expect("Error flushing stdout!");
-
-        let mut input = String::new();
-
-        io::stdin().read_line(&mut input)
-                   .expect("Error reading stdin!");
-
-        if input.ends_with('\n') {
-           input.pop();
-        }
-
-        let words: Vec<&str> = input.split_whitespace().collect();
-
-        if words.is_empty() {
-            println!("Invalid input. Try again.");
-        } else if input.to_string() == "re-roll".to_string() {
-
-            stats = Vec::new();
-
-            for _i in 0..5 {
-                stats.push(rand::thread_rng().gen_range(1,18));
-            }
-
-            println!("\nRolled stats:");
-            println!("===============");
-
-            let mut count = 0;
-
-            for stat in &stats {
-
-                println!("{}: {}", stat_names[count], stat);
-                count = count + 1;
-            }
-
-            println!("===============");
-            println!("What would you like to do? (You can type: accept, re-roll, explain or quit)");
-            println!("\n");
+/// If the character has a race its modifiers are applied to the stats after they are made.
+///
+/// Returns None when the player quits, or the input runs out.
+pub fn create_stats(mut character: Character, methods: &Vec<StatMethod>, console: &mut dyn Console, rng: &mut Rng) -> Option<Character> {
+    let method = choose_method(methods, console)?;
 
-        } else if input.to_string() == "accept".to_string() {
-            done = true;
+    let mut stats = generate(&method, console, rng)?;
 
-            let stats = accept_stats(&stats);
+    if let Some(race) = &character.race {
+        if !race.modifiers.is_empty() {
+            race.apply(&mut stats);
 
-            character.stats = Some(stats);
-        } else if input.to_string() == "quit".to_string() {
-            println!("Bye now!");
-            process::exit(1);
+            console.println(&format!("As a {} ({}) your stats are: {}", race.name, race.modifier_summary(), stats.summary()));
         }
     }
 
-    return character;
-}
-
-fn accept_stats(stats: &Vec<i32>) -> Stats {
-    println!("\nAccepted stats:, {:?}", stats);
+    character.stats = Some(stats);
 
-    return Stats::new(stats[0], stats[1], stats[2], stats[3], stats[4]);
+    return Some(character);
 }
@@ -0,0 +1,251 @@
+use std::vec::Vec;
+use core::console::Console;
+use core::dice::{Dice, Keep};
+use core::rng::Rng;
+use core::stats::Stat;
+use crate::character::charactersheet::Stats;
+
+/// How a player makes their stats. The methods live in core, so adventure files can name them.
+pub use core::stat_methods::{StatMethod, STANDARD_ARRAY, POINT_BUY_BUDGET};
+
+use core::stat_methods::{POINT_BUY_MIN, POINT_BUY_MAX};
+
+/// Make the stats with the method.
+///
+/// Returns None when the player quits, or the input runs out.
+pub fn generate(method: &StatMethod, console: &mut dyn Console, rng: &mut Rng) -> Option<Stats> {
+    match method {
+        StatMethod::Roll => roll_in_order(&Dice::new(3, 6), console, rng),
+        StatMethod::DropLowest => roll_in_order(&Dice::new(4, 6).keep(Keep::Highest(3)), console, rng),
+        StatMethod::StandardArray => assign(STANDARD_ARRAY.to_vec(), console),
+        StatMethod::PointBuy(budget) => point_buy(*budget, console),
+        StatMethod::Manual => {
+            let dice = Dice::new(3, 6);
+
+            let mut values = Vec::new();
+
+            console.println("\nRolled values:");
+            console.println("===============");
+
+            for _stat in Stat::all() {
+                let roll = dice.roll(rng);
+
+                console.println(&format!("{} ({})", roll.total, roll.breakdown()));
+
+                values.push(roll.total);
+            }
+
+            console.println("===============");
+
+            assign(values, console)
+        },
+    }
+}
+
+/// Ask the player how they want to make their stats.
+///
+/// When the adventure only allows one method there is nothing to choose. Returns None when the
+/// player quits, or the input runs out.
+pub fn choose_method(methods: &Vec<StatMethod>, console: &mut dyn Console) -> Option<StatMethod> {
+    if methods.len() == 1 {
+        return Some(methods[0]);
+    }
+
+    console.println("\nHow would you like to make your stats?");
+
+    for (index, method) in methods.iter().enumerate() {
+        console.println(&format!("{}) {} - {}", index + 1, method.name(), method.description()));
+    }
+
+    console.println("Type the number or the name, or quit to exit.");
+    console.println("\n");
+
+    loop {
+        let input = read_input(console)?;
+
+        let chosen = match input.parse::<usize>() {
+            Ok(number) if number >= 1 && number <= methods.len() => Some(methods[number - 1]),
+            _ => methods.iter().find(|method| method.name() == input).copied(),
+        };
+
+        match chosen {
+            Some(method) => return Some(method),
+            None => console.println("That's not one of the choices. Try again."),
+        }
+    }
+}
+
+/// Read what the player typed, lower cased.
+///
+/// Returns None when the player quits, or the input runs out. It is up to whoever made the
+/// character to leave the game.
+fn read_input(console: &mut dyn Console) -> Option<String> {
+    let input = match console.prompt() {
+        Some(input) => input.trim().to_lowercase(),
+        None => "quit".to_string(),
+    };
+
+    if input == "quit" {
+        console.println("Bye now!");
+        return None;
+    }
+
+    return Some(input);
+}
+
+fn join(values: &[i32]) -> String {
+    let values: Vec<String> = values.iter().map(|value| value.to_string()).collect();
+
+    return values.join(", ");
+}
+
+/// Roll the dice for each stat, in order, until the player accepts them.
+fn roll_in_order(dice: &Dice, console: &mut dyn Console, rng: &mut Rng) -> Option<Stats> {
+    let mut stats = roll_stats(dice, console, rng);
+
+    loop {
+        let input = read_input(console)?;
+
+        match input.as_str() {
+            "" => console.println("Invalid input. Try again."),
+            "re-roll" => stats = roll_stats(dice, console, rng),
+            "accept" => {
+                let stats = Stats::new(stats[0], stats[1], stats[2], stats[3], stats[4]);
+
+                console.println(&format!("\nAccepted stats: {}", stats.summary()));
+
+                return Some(stats);
+            },
+            _ => console.println("You can type: accept, re-roll or quit."),
+        }
+    }
+}
+
+/// Roll the dice for each stat and show the player what they got.
+fn roll_stats(dice: &Dice, console: &mut dyn Console, rng: &mut Rng) -> Vec<i32> {
+    console.println("\nRolled stats:");
+    console.println("===============");
+
+    let mut stats = Vec::new();
+
+    for stat in Stat::all() {
+        let roll = dice.roll(rng);
+
+        console.println(&format!("{}: {} ({})", stat.name(), roll.total, roll.breakdown()));
+
+        stats.push(roll.total);
+    }
+
+    console.println("===============");
+    console.println("What would you like to do? (You can type: accept, re-roll or quit)");
+    console.println("\n");
+
+    return stats;
+}
+
+/// Let the player give each value to a stat of their choice.
+///
+/// The last value goes to the last stat with out asking.
+fn assign(values: Vec<i32>, console: &mut dyn Console) -> Option<Stats> {
+    let mut stats = Stats::new(0, 0, 0, 0, 0);
+    let mut remaining = Stat::all();
+
+    console.println(&format!("\nYour values are: {}", join(&values)));
+
+    for value in values {
+        if remaining.len() == 1 {
+            stats.set(remaining[0], value);
+
+            console.println(&format!("{} gets {}.", remaining[0].name(), value));
+
+            break;
+        }
+
+        let names: Vec<&str> = remaining.iter().map(|stat| stat.name()).collect();
+
+        console.println(&format!("Which stat gets {}? ({})", value, names.join(", ")));
+
+        loop {
+            let input = read_input(console)?;
+
+            match Stat::from_name(&input) {
+                Some(stat) if remaining.contains(&stat) => {
+                    stats.set(stat, value);
+                    remaining.retain(|remaining| *remaining != stat);
+                    break;
+                },
+                Some(stat) => console.println(&format!("{} already has a value. Pick one of: {}", stat.name(), names.join(", "))),
+                None => console.println(&format!("That's not a stat. Pick one of: {}", names.join(", "))),
+            }
+        }
+    }
+
+    console.println(&format!("\nAccepted stats: {}", stats.summary()));
+
+    return Some(stats);
+}
+
+/// What it costs to buy a stat up to the value, starting from 8.
+///
+/// Each step up to 13 costs a point. 14 and 15 cost two points each.
+fn point_cost(value: i32) -> i32 {
+    match value {
+        14 => 7,
+        15 => 9,
+        _ => value - POINT_BUY_MIN,
+    }
+}
+
+/// Let the player spend the budget to raise their stats.
+fn point_buy(budget: i32, console: &mut dyn Console) -> Option<Stats> {
+    let mut stats = Stats::new(POINT_BUY_MIN, POINT_BUY_MIN, POINT_BUY_MIN, POINT_BUY_MIN, POINT_BUY_MIN);
+
+    console.println(&format!("\nPoint buy: every stat starts at {} and you have {} points to spend.", POINT_BUY_MIN, budget));
+    console.println(&format!("Each step up to 13 costs a point, 14 and 15 cost two points each. Stats can go up to {}.", POINT_BUY_MAX));
+    console.println("Type: STAT VALUE to set a stat, for example: str 14. You can also type reset or accept.");
+
+    loop {
+        let spent: i32 = Stat::all().iter().map(|stat| point_cost(stats.get(*stat))).sum();
+
+        console.println(&format!("\n{} (points left: {})", stats.summary(), budget - spent));
+
+        let input = read_input(console)?;
+        let words: Vec<&str> = input.split_whitespace().collect();
+
+        match words.as_slice() {
+            ["accept"] => {
+                console.println(&format!("\nAccepted stats: {}", stats.summary()));
+
+                return Some(stats);
+            },
+            ["reset"] => stats = Stats::new(POINT_BUY_MIN, POINT_BUY_MIN, POINT_BUY_MIN, POINT_BUY_MIN, POINT_BUY_MIN),
+            [name, value] => {
+                let stat = match Stat::from_name(name) {
+                    Some(stat) => stat,
+                    None => {
+                        console.println("That's not a stat. Use one of: str, int, dex, chr or dur.");
+                        continue;
+                    }
+                };
+
+                let value = match value.parse::<i32>() {
+                    Ok(value) if value >= POINT_BUY_MIN && value <= POINT_BUY_MAX => value,
+                    _ => {
+                        console.println(&format!("Stats can be from {} to {}.", POINT_BUY_MIN, POINT_BUY_MAX));
+                        continue;
+                    }
+                };
+
+                let cost = spent - point_cost(stats.get(stat)) + point_cost(value);
+
+                if cost > budget {
+                    console.println(&format!("You don't have enough points for that. It would cost {} of your {}.", cost, budget));
+                    continue;
+                }
+
+                stats.set(stat, value);
+            },
+            _ => console.println("Type: STAT VALUE, for example: str 14, or reset or accept."),
+        }
+    }
+}
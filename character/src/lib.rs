@@ -3,3 +3,4 @@ mod character;
 #[macro_use] extern crate prettytable;
 
 pub use crate::character::charactersheet;
+pub use crate::character::stat_methods;
@@ -3,3 +3,8 @@ pub mod process_call_backs;
 pub mod create_adventures;
 pub mod text_handeling;
 pub mod stat_bonus;
+pub mod stats;
+pub mod stat_methods;
+pub mod console;
+pub mod rng;
+pub mod dice;
@@ -0,0 +1,156 @@
+use std::fmt;
+use std::io;
+use std::io::Write;
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+/// Where the game reads the players input from and writes its text to.
+///
+/// Everything that talks to the player goes through a console, so the game can be played
+/// in a terminal or driven from a test with a MemoryConsole.
+pub trait Console: fmt::Debug {
+
+    /// Read a line of input, with out the new line at the end.
+    ///
+    /// Returns None when there is no more input.
+    fn read_line(&mut self) -> Option<String>;
+
+    /// Write text as is.
+    fn print(&mut self, text: &str);
+
+    /// Write text followed by a new line.
+    fn println(&mut self, text: &str) {
+        self.print(text);
+        self.print("\n");
+    }
+
+    /// Show the prompt and read the players input.
+    fn prompt(&mut self) -> Option<String> {
+        self.print("> ");
+
+        return self.read_line();
+    }
+}
+
+/// Reads from stdin and writes to stdout.
+#[derive(Debug, Default)]
+pub struct Terminal;
+
+impl Terminal {
+
+    /// Create a new terminal.
+    pub fn new() -> Self {
+        Terminal
+    }
+}
+
+impl Console for Terminal {
+    fn read_line(&mut self) -> Option<String> {
+        let mut input = String::new();
+
+        let read = io::stdin().read_line(&mut input)
+                              .expect("Error reading stdin!");
+
+        if read == 0 {
+            return None;
+        }
+
+        return Some(trim_new_line(input));
+    }
+
+    fn print(&mut self, text: &str) {
+        print!("{}", text);
+
+        io::stdout().flush().expect("Error flushing stdout!");
+    }
+}
+
+/// Everything a MemoryConsole has written.
+///
+/// This is shared with the console, so a test can hold on to it after the console has been
+/// handed to the game.
+#[derive(Clone, Debug, Default)]
+pub struct Output {
+    text: Rc<RefCell<String>>,
+}
+
+impl Output {
+
+    /// All of the text written so far.
+    pub fn text(&self) -> String {
+        return self.text.borrow().clone();
+    }
+
+    /// Has the text been written?
+    pub fn contains(&self, text: &str) -> bool {
+        return self.text.borrow().contains(text);
+    }
+
+    /// Forget everything written so far.
+    pub fn clear(&self) {
+        self.text.borrow_mut().clear();
+    }
+
+    fn push(&self, text: &str) {
+        self.text.borrow_mut().push_str(text);
+    }
+}
+
+/// Reads from a list of lines and writes to memory.
+///
+/// Each line read is written back after the prompt, the way it would show up in a terminal,
+/// so the output reads like a transcript of the game.
+#[derive(Debug)]
+pub struct MemoryConsole {
+    input: VecDeque<String>,
+    output: Output,
+}
+
+impl MemoryConsole {
+
+    /// Create a console that will read the lines in order.
+    pub fn new(input: Vec<&str>) -> Self {
+        MemoryConsole {
+            input: input.iter().map(|line| line.to_string()).collect(),
+            output: Output::default(),
+        }
+    }
+
+    /// Add more lines to read.
+    pub fn push_input(&mut self, line: &str) {
+        self.input.push_back(line.to_string());
+    }
+
+    /// The output of the console.
+    pub fn output(&self) -> Output {
+        return self.output.clone();
+    }
+}
+
+impl Console for MemoryConsole {
+    fn read_line(&mut self) -> Option<String> {
+        let line = self.input.pop_front()?;
+
+        self.output.push(&line);
+        self.output.push("\n");
+
+        return Some(line);
+    }
+
+    fn print(&mut self, text: &str) {
+        self.output.push(text);
+    }
+}
+
+fn trim_new_line(mut input: String) -> String {
+    if input.ends_with('\n') {
+        input.pop();
+    }
+
+    if input.ends_with('\r') {
+        input.pop();
+    }
+
+    return input;
+}
@@ -0,0 +1,246 @@
+use std::fmt;
+use std::vec::Vec;
+use crate::core::rng::Rng;
+
+/// Which of the dice count towards the total.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Keep {
+    All,
+    Highest(u32),
+    Lowest(u32),
+}
+
+/// A dice expression, such as: 3d6, 1d20+2, 4d6kh3 or 2d20kh1.
+///
+/// The expression is written as: count d sides, then optionally kh or kl and how many of the
+/// highest or lowest dice to keep, then optionally + or - a modifier. The count can be left
+/// off, so d20 is the same as 1d20.
+///
+/// Rolling two d20 and keeping the highest (2d20kh1) is advantage, keeping the lowest
+/// (2d20kl1) is disadvantage.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Dice {
+    pub count: u32,
+    pub sides: u32,
+    pub keep: Keep,
+    pub modifier: i32,
+}
+
+/// Why a dice expression could not be read.
+#[derive(Debug)]
+pub struct DiceError {
+    pub expression: String,
+    pub message: String,
+}
+
+impl fmt::Display for DiceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}", self.expression, self.message)
+    }
+}
+
+impl std::error::Error for DiceError {}
+
+/// The most dice we will roll at once.
+const MAX_DICE: u32 = 100;
+
+/// The most sides a die can have.
+const MAX_SIDES: u32 = 1000;
+
+/// The biggest modifier, up or down, so a total always fits in an i32.
+const MAX_MODIFIER: i32 = 1000;
+
+impl Dice {
+
+    /// Roll count dice with the number of sides, keeping all of them.
+    pub fn new(count: u32, sides: u32) -> Self {
+        Dice {
+            count: count,
+            sides: sides,
+            keep: Keep::All,
+            modifier: 0,
+        }
+    }
+
+    /// Only keep some of the dice.
+    pub fn keep(mut self, keep: Keep) -> Self {
+        self.keep = keep;
+
+        return self;
+    }
+
+    /// Add the modifier to the total, for example a stat bonus.
+    pub fn with_modifier(mut self, modifier: i32) -> Self {
+        self.modifier = modifier;
+
+        return self;
+    }
+
+    /// Read a dice expression, ignoring case and spaces.
+    pub fn parse(expression: &str) -> Result<Dice, DiceError> {
+        let text: String = expression.chars().filter(|character| !character.is_whitespace()).collect::<String>().to_lowercase();
+
+        let fail = |message: &str| DiceError { expression: expression.to_string(), message: message.to_string() };
+
+        let (count, rest) = match text.find('d') {
+            Some(index) => (&text[..index], &text[index + 1..]),
+            None => return Err(fail("expected a d, for example: 3d6.")),
+        };
+
+        let count = if count.is_empty() { 1 } else { count.parse::<u32>().map_err(|_| fail("the number of dice must be a number."))? };
+
+        let (rest, modifier) = match rest.find(|character| character == '+' || character == '-') {
+            Some(index) => {
+                let modifier = rest[index + 1..].parse::<i32>().map_err(|_| fail("the modifier must be a number."))?;
+
+                (&rest[..index], if &rest[index..index + 1] == "-" { -modifier } else { modifier })
+            },
+            None => (rest, 0),
+        };
+
+        let (sides, keep) = match rest.find('k') {
+            Some(index) => {
+                let amount = |text: &str| text.parse::<u32>().map_err(|_| fail("the number of dice to keep must be a number."));
+
+                let keep = match &rest[index + 1..] {
+                    keep if keep.starts_with('h') => Keep::Highest(amount(&keep[1..])?),
+                    keep if keep.starts_with('l') => Keep::Lowest(amount(&keep[1..])?),
+                    _ => return Err(fail("expected kh or kl, for example: 4d6kh3.")),
+                };
+
+                (&rest[..index], keep)
+            },
+            None => (rest, Keep::All),
+        };
+
+        let sides = sides.parse::<u32>().map_err(|_| fail("the number of sides must be a number."))?;
+
+        if count == 0 || count > MAX_DICE {
+            return Err(fail(&format!("the number of dice must be from 1 to {}.", MAX_DICE)));
+        }
+
+        if sides == 0 || sides > MAX_SIDES {
+            return Err(fail(&format!("dice must have from 1 to {} sides.", MAX_SIDES)));
+        }
+
+        if modifier.abs() > MAX_MODIFIER {
+            return Err(fail(&format!("the modifier must be from -{} to {}.", MAX_MODIFIER, MAX_MODIFIER)));
+        }
+
+        match keep {
+            Keep::Highest(kept) | Keep::Lowest(kept) if kept == 0 || kept > count => {
+                return Err(fail("you can only keep from 1 to all of the dice."));
+            },
+            _ => {},
+        }
+
+        return Ok(Dice::new(count, sides).keep(keep).with_modifier(modifier));
+    }
+
+    /// Roll the dice.
+    pub fn roll(&self, rng: &mut Rng) -> Roll {
+        let dice: Vec<i32> = (0..self.count).map(|_| rng.gen_range(1, self.sides as i32 + 1)).collect();
+
+        let mut order: Vec<usize> = (0..dice.len()).collect();
+
+        // Highest first, so keeping the highest keeps the front and keeping the lowest keeps the back.
+        order.sort_by(|a, b| dice[*b].cmp(&dice[*a]));
+
+        let kept_positions: Vec<usize> = match self.keep {
+            Keep::All => order,
+            Keep::Highest(amount) => order.into_iter().take(amount as usize).collect(),
+            Keep::Lowest(amount) => order.into_iter().rev().take(amount as usize).collect(),
+        };
+
+        let kept: Vec<bool> = (0..dice.len()).map(|position| kept_positions.contains(&position)).collect();
+
+        let total = dice.iter().zip(&kept).filter(|(_, kept)| **kept).map(|(die, _)| die).sum::<i32>() + self.modifier;
+
+        return Roll {
+            expression: self.to_string(),
+            dice: dice,
+            kept: kept,
+            modifier: self.modifier,
+            total: total,
+        };
+    }
+}
+
+impl fmt::Display for Dice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}d{}", self.count, self.sides)?;
+
+        match self.keep {
+            Keep::All => {},
+            Keep::Highest(amount) => write!(f, "kh{}", amount)?,
+            Keep::Lowest(amount) => write!(f, "kl{}", amount)?,
+        }
+
+        if self.modifier > 0 {
+            write!(f, "+{}", self.modifier)?;
+        } else if self.modifier < 0 {
+            write!(f, "{}", self.modifier)?;
+        }
+
+        return Ok(());
+    }
+}
+
+/// The result of rolling dice.
+///
+/// Dice are in the order they were rolled. Kept says which of them count towards the total.
+#[derive(Clone, Debug)]
+pub struct Roll {
+    pub expression: String,
+    pub dice: Vec<i32>,
+    pub kept: Vec<bool>,
+    pub modifier: i32,
+    pub total: i32,
+}
+
+impl Roll {
+
+    /// The dice that count towards the total.
+    pub fn kept_dice(&self) -> Vec<i32> {
+        return self.dice.iter().zip(&self.kept).filter(|(_, kept)| **kept).map(|(die, _)| *die).collect();
+    }
+
+    /// The dice that were thrown away.
+    pub fn dropped_dice(&self) -> Vec<i32> {
+        return self.dice.iter().zip(&self.kept).filter(|(_, kept)| !**kept).map(|(die, _)| *die).collect();
+    }
+
+    /// How the total was made, for example: 1d20+2: 12 + 2 or 4d6kh3: 6 + 4 + 3, dropped 1.
+    pub fn breakdown(&self) -> String {
+        let kept: Vec<String> = self.kept_dice().iter().map(|die| die.to_string()).collect();
+
+        let mut breakdown = format!("{}: {}", self.expression, kept.join(" + "));
+
+        if self.modifier > 0 {
+            breakdown = format!("{} + {}", breakdown, self.modifier);
+        } else if self.modifier < 0 {
+            breakdown = format!("{} - {}", breakdown, -self.modifier);
+        }
+
+        let dropped = self.dropped_dice();
+
+        if !dropped.is_empty() {
+            let dropped: Vec<String> = dropped.iter().map(|die| die.to_string()).collect();
+
+            breakdown = format!("{}, dropped {}", breakdown, dropped.join(", "));
+        }
+
+        return breakdown;
+    }
+}
+
+impl fmt::Display for Roll {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} = {}", self.breakdown(), self.total)
+    }
+}
+
+/// Read the expression and roll it.
+pub fn roll(expression: &str, rng: &mut Rng) -> Result<Roll, DiceError> {
+    return Ok(Dice::parse(expression)?.roll(rng));
+}
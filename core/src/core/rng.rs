@@ -0,0 +1,93 @@
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The seed and state of the generator the game is rolling with, see report.
+static REPORTED: Mutex<Option<(u64, u64)>> = Mutex::new(None);
+
+/// The random number generator for everything the game rolls.
+///
+/// It is seeded, so the same seed always gives the same rolls. That lets a test play an
+/// adventure and know what each DC check will roll.
+///
+/// This is SplitMix64. It's small, fast and good enough for dice, and because it lives here
+/// the rolls for a seed don't change when a dependency is updated.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    seed: u64,
+    state: u64,
+}
+
+impl Rng {
+
+    /// Create a generator from the seed.
+    pub fn new(seed: u64) -> Self {
+        Rng {
+            seed: seed,
+            state: seed,
+        }
+    }
+
+    /// Create a generator seeded from the clock, for when we don't care about the seed.
+    pub fn from_time() -> Self {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH)
+                                     .map(|duration| duration.as_nanos() as u64)
+                                     .unwrap_or(0);
+
+        return Rng::new(nanos);
+    }
+
+    /// The seed the generator was created with.
+    pub fn seed(&self) -> u64 {
+        return self.seed;
+    }
+
+    /// Pick up a generator from where it left off, for example when a game is loaded.
+    pub fn from_state(seed: u64, state: u64) -> Self {
+        Rng {
+            seed: seed,
+            state: state,
+        }
+    }
+
+    /// Where the generator is up to. Together with the seed this is enough to carry on
+    /// rolling where we left off.
+    pub fn state(&self) -> u64 {
+        return self.state;
+    }
+
+    /// The next random number.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+
+        let mut z = self.state;
+
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+
+        return z ^ (z >> 31);
+    }
+
+    /// A number from low up to, but not including, high.
+    ///
+    /// Panics if low is not less then high.
+    pub fn gen_range(&mut self, low: i32, high: i32) -> i32 {
+        assert!(low < high, "gen_range called with an empty range: {} to {}", low, high);
+
+        let span = (high as i64 - low as i64) as u64;
+
+        return (low as i64 + (self.next_u64() % span) as i64) as i32;
+    }
+}
+
+/// Keep track of the generator the game is rolling with, so when the game crashes we can say
+/// what it was, even after a save with a different seed has been loaded.
+pub fn report(rng: &Rng) {
+    if let Ok(mut reported) = REPORTED.lock() {
+        *reported = Some((rng.seed(), rng.state()));
+    }
+}
+
+/// The seed and state of the generator last given to report, if there was one.
+pub fn reported() -> Option<(u64, u64)> {
+    return REPORTED.lock().ok().and_then(|reported| *reported);
+}
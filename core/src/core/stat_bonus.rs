@@ -1,10 +1,11 @@
+/// The bonus for each stat value, from 0 up to 20. Index it with the stat value.
 pub fn create_all_stat_bonuses() -> Vec<i32> {
 
     let mut stat_bonus: Vec<i32> = Vec::new();
 
     stat_bonus.push(-6);
 
-    for i in 1..18 {
+    for i in 1..21 {
         match i {
             1 => stat_bonus.push(-4),
             2 | 3 => stat_bonus.push(-3),
@@ -15,7 +16,8 @@ pub fn create_all_stat_bonuses() -> Vec<i32> {
             12 | 13 => stat_bonus.push(2),
             14 | 15 => stat_bonus.push(3),
             16 | 17 => stat_bonus.push(4),
-            18  => stat_bonus.push(5),
+            18 | 19 => stat_bonus.push(5),
+            20 => stat_bonus.push(6),
             _ => panic!("Oops!"),
         }
     }
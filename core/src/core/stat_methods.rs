@@ -0,0 +1,98 @@
+/// The values handed out with the standard array, highest first.
+pub const STANDARD_ARRAY: [i32; 5] = [15, 14, 13, 12, 10];
+
+/// The usual number of points to spend with point buy.
+pub const POINT_BUY_BUDGET: i32 = 27;
+
+/// What every stat starts at with point buy, and the lowest it can go.
+pub const POINT_BUY_MIN: i32 = 8;
+
+/// The highest a stat can be bought to with point buy.
+pub const POINT_BUY_MAX: i32 = 15;
+
+/// The ways a player can make their stats.
+///
+/// Each adventure decides which of these the player can pick from, for example an
+/// adventure might only allow point buy so every character starts out even.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum StatMethod {
+
+    /// Roll 3d6 for each stat, in order. The player can re-roll as often as they like.
+    Roll,
+
+    /// Roll 4d6 for each stat and drop the lowest die, in order.
+    DropLowest,
+
+    /// Give the values of the standard array to the stats of the players choice.
+    StandardArray,
+
+    /// Every stat starts at 8 and the player spends the budget to raise them.
+    PointBuy(i32),
+
+    /// Roll 3d6 five times and give each value to the stat of the players choice.
+    Manual,
+}
+
+impl StatMethod {
+
+    /// Every method, with the usual point buy budget.
+    pub fn all() -> Vec<StatMethod> {
+        return vec![
+            StatMethod::Roll,
+            StatMethod::DropLowest,
+            StatMethod::StandardArray,
+            StatMethod::PointBuy(POINT_BUY_BUDGET),
+            StatMethod::Manual,
+        ];
+    }
+
+    /// Get a method from how an adventure file names it, for example: roll or point_buy:20.
+    ///
+    /// Point buy without a budget uses the usual one. Returns None for anything else, or a
+    /// budget that isn't a number above zero.
+    pub fn from_name(name: &str) -> Option<StatMethod> {
+        let name = name.trim().to_lowercase();
+
+        let (method, budget) = match name.find(':') {
+            Some(index) => (&name[..index], Some(&name[index + 1..])),
+            None => (name.as_str(), None),
+        };
+
+        match (method, budget) {
+            ("roll", None) => Some(StatMethod::Roll),
+            ("4d6", None) | ("drop_lowest", None) => Some(StatMethod::DropLowest),
+            ("array", None) | ("standard_array", None) => Some(StatMethod::StandardArray),
+            ("point_buy", None) | ("point-buy", None) => Some(StatMethod::PointBuy(POINT_BUY_BUDGET)),
+            ("point_buy", Some(budget)) | ("point-buy", Some(budget)) => match budget.trim().parse::<i32>() {
+                Ok(budget) if budget > 0 => Some(StatMethod::PointBuy(budget)),
+                _ => None,
+            },
+            ("manual", None) => Some(StatMethod::Manual),
+            _ => None,
+        }
+    }
+
+    /// What the player types to pick the method.
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatMethod::Roll => "roll",
+            StatMethod::DropLowest => "4d6",
+            StatMethod::StandardArray => "array",
+            StatMethod::PointBuy(_) => "point-buy",
+            StatMethod::Manual => "manual",
+        }
+    }
+
+    /// What the method does, as shown to the player.
+    pub fn description(&self) -> String {
+        let array: Vec<String> = STANDARD_ARRAY.iter().map(|value| value.to_string()).collect();
+
+        match self {
+            StatMethod::Roll => "Roll 3d6 for each stat, in order.".to_string(),
+            StatMethod::DropLowest => "Roll 4d6 for each stat and drop the lowest die, in order.".to_string(),
+            StatMethod::StandardArray => format!("Give the values {} to the stats you choose.", array.join(", ")),
+            StatMethod::PointBuy(budget) => format!("Every stat starts at {}, spend {} points to raise them.", POINT_BUY_MIN, budget),
+            StatMethod::Manual => "Roll 3d6 five times and give each value to the stat you choose.".to_string(),
+        }
+    }
+}
@@ -0,0 +1,38 @@
+/// The stats a character has.
+///
+/// Used by anything that needs to name a stat, such as a DEX check on an exit.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Stat {
+    Str, Int, Dex, Chr, Dur
+}
+
+impl Stat {
+
+    /// Every stat, in the order they are shown to the player.
+    pub fn all() -> Vec<Stat> {
+        return vec![Stat::Str, Stat::Int, Stat::Dex, Stat::Chr, Stat::Dur];
+    }
+
+    /// Get a stat from its short or long name, for example: dex or dexterity.
+    pub fn from_name(name: &str) -> Option<Stat> {
+        match name.to_lowercase().as_str() {
+            "str" | "strength" => Some(Stat::Str),
+            "int" | "intelligence" => Some(Stat::Int),
+            "dex" | "dexterity" => Some(Stat::Dex),
+            "chr" | "charisma" => Some(Stat::Chr),
+            "dur" | "durability" => Some(Stat::Dur),
+            _ => None,
+        }
+    }
+
+    /// The short name of the stat, as shown to the player.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Stat::Str => "str",
+            Stat::Int => "int",
+            Stat::Dex => "dex",
+            Stat::Chr => "chr",
+            Stat::Dur => "dur",
+        }
+    }
+}
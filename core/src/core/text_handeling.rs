@@ -5,3 +5,88 @@ pub fn unwrap_str<'a>(w: Option<&'a String>) -> &'a str {
         None    => ""
     }
 }
+
+/// How many letters have to be added, removed, changed or swapped with the letter next to them
+/// to turn one word into the other, ignoring case.
+///
+/// For example: "kye" is 1 away from "key" and "lok" is 1 away from "look".
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+
+    // distances[i][j] is the distance between the first i letters of a and the first j letters of b.
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for i in 0..=a.len() {
+        distances[i][0] = i;
+    }
+
+    for j in 0..=b.len() {
+        distances[0][j] = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+
+            distances[i][j] = distance;
+        }
+    }
+
+    return distances[a.len()][b.len()];
+}
+
+/// Is what the player typed close enough to the word to be a typo of it?
+///
+/// Short words have to be spelled right, words up to five letters can be one letter off and
+/// longer words two.
+pub fn is_close(typed: &str, word: &str) -> bool {
+    let allowed = match typed.chars().count() {
+        0..=2 => 0,
+        3..=5 => 1,
+        _ => 2,
+    };
+
+    return edit_distance(typed, word) <= allowed;
+}
+
+/// The words that are closest to what the player typed, as long as they are close enough to be
+/// a typo of it. More than one word comes back when they are just as close.
+pub fn closest<'a>(typed: &str, words: &[&'a str]) -> Vec<&'a str> {
+    let close: Vec<(&str, usize)> = words.iter()
+        .filter(|word| is_close(typed, word))
+        .map(|word| (*word, edit_distance(typed, word)))
+        .collect();
+
+    let best = match close.iter().map(|(_, distance)| *distance).min() {
+        Some(best) => best,
+        None => return Vec::new(),
+    };
+
+    let mut found: Vec<&str> = Vec::new();
+
+    for (word, distance) in close {
+        if distance == best && !found.contains(&word) {
+            found.push(word);
+        }
+    }
+
+    return found;
+}
+
+/// Join the words for a question, for example: "look, lock or loot".
+pub fn or_list(words: &[String]) -> String {
+    match words {
+        [] => String::new(),
+        [word] => word.clone(),
+        [rest @ .., last] => format!("{} or {}", rest.join(", "), last),
+    }
+}
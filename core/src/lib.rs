@@ -5,3 +5,8 @@ pub use crate::core::process_call_backs;
 pub use crate::core::create_adventures;
 pub use crate::core::text_handeling;
 pub use crate::core::stat_bonus;
+pub use crate::core::stats;
+pub use crate::core::stat_methods;
+pub use crate::core::console;
+pub use crate::core::rng;
+pub use crate::core::dice;
@@ -0,0 +1,6 @@
+pub mod save;
+pub mod parser;
+pub mod commands;
+mod combat;
+mod experience;
+pub mod target;
@@ -0,0 +1,481 @@
+use std::vec::Vec;
+use core::dice::{Dice, Roll};
+use core::stats::Stat;
+use core::text_handeling::or_list;
+use world::context::Context;
+use world::enemy::Enemy;
+use crate::Game;
+use crate::game::target::MIN_PARTIAL;
+
+/// The players armor class before their dex bonus.
+const BASE_ARMOR_CLASS: i32 = 10;
+
+/// What defending adds to the players armor class until their next turn.
+const DEFEND_BONUS: i32 = 2;
+
+/// What the player has to beat with a dex check to run away.
+const FLEE_DC: i32 = 10;
+
+/// Who is taking a turn.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Turn {
+    Player,
+    Enemy(usize),
+}
+
+/// How the players turn went.
+#[derive(PartialEq, Debug)]
+enum Outcome {
+    Done,
+    Fled,
+    Quit,
+}
+
+/// The class ability a player can use once a fight.
+///
+/// - MagicMissile, for classes that can cast: glowing darts that always hit for 3d4.
+/// - SneakAttack, for classes that can steal: an attack rolled with dex that does an extra 2d6.
+/// - PowerAttack, for classes that can force: an attack at -2 to hit that rolls twice the damage dice.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Ability {
+    MagicMissile,
+    SneakAttack,
+    PowerAttack,
+}
+
+/// A fight between the player and enemies in the room.
+///
+/// Everyone rolls initiative, a d20 plus the players dex bonus or the enemies initiative, and
+/// takes turns from highest to lowest. The player goes first on a tie.
+///
+/// On their turn the player can:
+///
+/// - attack: roll a d20 plus their str bonus, hitting when it meets or beats the enemies armor
+///   class, then roll the damage dice of their class plus their str bonus.
+/// - defend: add 2 to their armor class until their next turn.
+/// - flee: roll a dex check against DC 10 to run back the way they came.
+/// - ability: use the ability of their class, once a fight. See Ability.
+///
+/// Enemies attack the player on their turn, hitting when their roll meets or beats 10 plus the
+/// players dex bonus.
+///
+/// The fight ends when every enemy is defeated, or the player flees, is defeated or quits.
+struct Combat {
+    enemies: Vec<Enemy>,
+    order: Vec<Turn>,
+    defending: bool,
+    ability_used: bool,
+    fled: bool,
+}
+
+impl Combat {
+
+    /// Is every enemy defeated?
+    fn won(&self) -> bool {
+        return self.enemies.iter().all(|enemy| enemy.is_defeated());
+    }
+}
+
+impl Game {
+
+    /// Fight the enemies in the room with these names.
+    ///
+    /// When the fight is over what happened to the enemies is kept in the room, defeated enemies
+    /// (and people with the same name) are removed from it.
+    pub(crate) fn fight(&mut self, names: Vec<String>) {
+        let enemies: Vec<Enemy> = self.current_room().enemies.iter()
+            .filter(|enemy| !enemy.is_defeated() && names.iter().any(|name| enemy.matches(name)))
+            .cloned()
+            .collect();
+
+        if enemies.is_empty() {
+            return;
+        }
+
+        let mut combat = Combat {
+            enemies: enemies,
+            order: Vec::new(),
+            defending: false,
+            ability_used: false,
+            fled: false,
+        };
+
+        self.console.println("\n=== Fight ===");
+
+        for enemy in &combat.enemies {
+            self.console.println(&format!("You face the {} (HP: {}/{}, AC: {}).", enemy.name, enemy.hp, enemy.max_hp, enemy.armor_class));
+        }
+
+        self.roll_initiative(&mut combat);
+
+        'fight: loop {
+            for turn in combat.order.clone() {
+                match turn {
+                    Turn::Player => {
+                        if self.player_turn(&mut combat) != Outcome::Done {
+                            break 'fight;
+                        }
+                    },
+                    Turn::Enemy(index) => {
+                        if !combat.enemies[index].is_defeated() {
+                            self.enemy_turn(&combat, index);
+                        }
+                    },
+                }
+
+                if combat.won() || self.game_character.is_defeated() {
+                    break 'fight;
+                }
+            }
+        }
+
+        self.end_fight(combat);
+    }
+
+    /// Everyone rolls a d20 to see who goes first.
+    fn roll_initiative(&mut self, combat: &mut Combat) {
+        let mut rolls: Vec<(Turn, i32)> = Vec::new();
+
+        let roll = Dice::new(1, 20).with_modifier(self.roll_bonus(Stat::Dex)).roll(&mut self.rng);
+
+        self.console.println(&format!("\nYou roll for initiative: {} ({})", roll.total, roll.breakdown()));
+
+        rolls.push((Turn::Player, roll.total));
+
+        for (index, enemy) in combat.enemies.iter().enumerate() {
+            let roll = Dice::new(1, 20).with_modifier(enemy.initiative).roll(&mut self.rng);
+
+            self.console.println(&format!("The {} rolls for initiative: {} ({})", enemy.name, roll.total, roll.breakdown()));
+
+            rolls.push((Turn::Enemy(index), roll.total));
+        }
+
+        // The sort keeps the order of ties and the player was added first, so they win them.
+        rolls.sort_by(|a, b| b.1.cmp(&a.1));
+
+        combat.order = rolls.into_iter().map(|(turn, _)| turn).collect();
+
+        let names: Vec<String> = combat.order.iter().map(|turn| match turn {
+            Turn::Player => "You".to_string(),
+            Turn::Enemy(index) => combat.enemies[*index].name.clone(),
+        }).collect();
+
+        self.console.println(&format!("Turn order: {}", names.join(", ")));
+    }
+
+    /// Ask the player what they do until they do something that takes their turn.
+    fn player_turn(&mut self, combat: &mut Combat) -> Outcome {
+        combat.defending = false;
+
+        loop {
+            self.console.println(&format!("\nYour turn. HP: {}/{}. You can: attack, defend, flee, ability or hp.", self.game_character.hp, self.game_character.max_hp()));
+
+            let input = match self.console.prompt() {
+                Some(input) => input.trim().to_lowercase(),
+                None => {
+                    self.quit_game();
+                    return Outcome::Quit;
+                },
+            };
+
+            let words: Vec<&str> = input.split_whitespace().collect();
+            let target = if words.len() > 1 { words[1..].join(" ") } else { "".to_string() };
+
+            match words.first().copied().unwrap_or("") {
+                "attack" | "hit" => {
+                    let index = match self.target(combat, &target) {
+                        Some(index) => index,
+                        None => continue,
+                    };
+
+                    let weapon = self.weapon();
+
+                    self.attack(combat, index, Stat::Str, 0, weapon, None);
+
+                    return Outcome::Done;
+                },
+                "defend" => {
+                    combat.defending = true;
+
+                    self.console.println(&format!("You raise your guard. Your AC is {} until your next turn.", self.armor_class(true)));
+
+                    return Outcome::Done;
+                },
+                "flee" | "run" => {
+                    if self.history.is_empty() || !self.current_room().go_back.can_go_back {
+                        self.console.println("There's nowhere to run!");
+                        continue;
+                    }
+
+                    let roll = Dice::new(1, 20).with_modifier(self.roll_bonus(Stat::Dex)).roll(&mut self.rng);
+
+                    if roll.total > FLEE_DC {
+                        self.console.println(&format!("You get away with a roll of: {} ({}).", roll.total, roll.breakdown()));
+
+                        combat.fled = true;
+
+                        return Outcome::Fled;
+                    }
+
+                    self.console.println(&format!("You try to run, but can't get away. You rolled: {} ({}).", roll.total, roll.breakdown()));
+
+                    return Outcome::Done;
+                },
+                "ability" => {
+                    let ability = match self.ability() {
+                        Some(ability) => ability,
+                        None => {
+                            self.console.println("You don't have an ability to use.");
+                            continue;
+                        }
+                    };
+
+                    if combat.ability_used {
+                        self.console.println("You have already used your ability this fight.");
+                        continue;
+                    }
+
+                    if !self.has_unlocked("ability") {
+                        continue;
+                    }
+
+                    let index = match self.target(combat, &target) {
+                        Some(index) => index,
+                        None => continue,
+                    };
+
+                    combat.ability_used = true;
+
+                    self.use_ability(combat, index, ability);
+
+                    return Outcome::Done;
+                },
+                "hp" | "health" => {
+                    self.console.println(&format!("HP: {}/{}, AC: {}", self.game_character.hp, self.game_character.max_hp(), self.armor_class(false)));
+
+                    for enemy in combat.enemies.iter().filter(|enemy| !enemy.is_defeated()) {
+                        self.console.println(&format!("{} HP: {}/{}, AC: {}", enemy.name, enemy.hp, enemy.max_hp, enemy.armor_class));
+                    }
+                },
+                "q" | "quit" | "exit" => {
+                    self.quit_game();
+                    return Outcome::Quit;
+                },
+                _ => self.console.println("You can't do that in a fight."),
+            }
+        }
+    }
+
+    /// The enemy the player is talking about, or the first one still standing when they
+    /// don't say.
+    ///
+    /// The full name wins, other wise part of a name will do as long as only one enemy has it
+    /// and it is at least three letters, the same as naming anything else.
+    fn target(&mut self, combat: &Combat, name: &str) -> Option<usize> {
+        let standing: Vec<usize> = (0..combat.enemies.len()).filter(|index| !combat.enemies[*index].is_defeated()).collect();
+
+        if name == "" {
+            return standing.first().copied();
+        }
+
+        if let Some(index) = standing.iter().find(|index| combat.enemies[**index].matches(name)) {
+            return Some(*index);
+        }
+
+        let name = name.to_lowercase();
+        let partial: Vec<usize> = standing.into_iter()
+            .filter(|index| name.chars().count() >= MIN_PARTIAL && combat.enemies[*index].name.to_lowercase().contains(&name))
+            .collect();
+
+        match partial.len() {
+            0 => self.console.println(&format!("There is no {} to fight.", name)),
+            1 => return Some(partial[0]),
+            _ => {
+                let labels: Vec<String> = partial.iter().map(|index| format!("the {}", combat.enemies[*index].name)).collect();
+
+                self.console.println(&format!("Which one: {}?", or_list(&labels)));
+            },
+        }
+
+        return None;
+    }
+
+    /// The damage dice of the players class, plus their str bonus.
+    fn weapon(&self) -> Dice {
+        let damage = match &self.game_character.class {
+            Some(class) => Dice::parse(&class.damage).unwrap_or(Dice::new(1, 4)),
+            None => Dice::new(1, 4),
+        };
+
+        let modifier = damage.modifier + self.roll_bonus(Stat::Str);
+
+        return damage.with_modifier(modifier);
+    }
+
+    /// The ability of the players class.
+    fn ability(&self) -> Option<Ability> {
+        let class = match &self.game_character.class {
+            Some(class) => class,
+            None => return None,
+        };
+
+        if class.can_cast {
+            return Some(Ability::MagicMissile);
+        }
+
+        if class.can_steal {
+            return Some(Ability::SneakAttack);
+        }
+
+        if class.can_force {
+            return Some(Ability::PowerAttack);
+        }
+
+        return None;
+    }
+
+    fn use_ability(&mut self, combat: &mut Combat, index: usize, ability: Ability) {
+        match ability {
+            Ability::MagicMissile => {
+                self.console.println(&format!("Glowing darts streak from your fingers towards the {}.", combat.enemies[index].name));
+
+                let roll = Dice::new(3, 4).roll(&mut self.rng);
+
+                self.hurt_enemy(combat, index, vec![roll]);
+            },
+            Ability::SneakAttack => {
+                self.console.println(&format!("You slip around the {} and strike where it hurts.", combat.enemies[index].name));
+
+                let weapon = self.weapon();
+
+                self.attack(combat, index, Stat::Dex, 0, weapon, Some(Dice::new(2, 6)));
+            },
+            Ability::PowerAttack => {
+                self.console.println(&format!("You put everything you have into a swing at the {}.", combat.enemies[index].name));
+
+                let mut weapon = self.weapon();
+
+                weapon.count = weapon.count * 2;
+
+                self.attack(combat, index, Stat::Str, -2, weapon, None);
+            },
+        }
+    }
+
+    /// Attack the enemy, hitting when the roll meets or beats its armor class.
+    fn attack(&mut self, combat: &mut Combat, index: usize, stat: Stat, to_hit: i32, damage: Dice, extra: Option<Dice>) {
+        let roll = Dice::new(1, 20).with_modifier(self.roll_bonus(stat) + to_hit).roll(&mut self.rng);
+
+        let name = combat.enemies[index].name.clone();
+        let armor_class = combat.enemies[index].armor_class;
+
+        if roll.total < armor_class {
+            self.console.println(&format!("You miss the {} with a roll of: {} ({}) against AC {}.", name, roll.total, roll.breakdown(), armor_class));
+            return;
+        }
+
+        self.console.println(&format!("You hit the {} with a roll of: {} ({}) against AC {}.", name, roll.total, roll.breakdown(), armor_class));
+
+        let mut rolls = vec![damage.roll(&mut self.rng)];
+
+        if let Some(extra) = extra {
+            rolls.push(extra.roll(&mut self.rng));
+        }
+
+        self.hurt_enemy(combat, index, rolls);
+    }
+
+    /// Take the damage from the enemy. A hit always does at least 1 damage.
+    fn hurt_enemy(&mut self, combat: &mut Combat, index: usize, rolls: Vec<Roll>) {
+        let total = rolls.iter().map(|roll| roll.total).sum::<i32>().max(1);
+        let breakdown: Vec<String> = rolls.iter().map(|roll| roll.breakdown()).collect();
+
+        let enemy = &mut combat.enemies[index];
+
+        enemy.hp = (enemy.hp - total).max(0);
+
+        let enemy = enemy.clone();
+
+        self.console.println(&format!("The {} takes {} damage ({}). {} HP: {}/{}", enemy.name, total, breakdown.join(", "), enemy.name, enemy.hp, enemy.max_hp));
+
+        if enemy.is_defeated() {
+            self.console.println(&format!("The {} is defeated!", enemy.name));
+
+            self.gain_xp(enemy.xp, &format!("defeating the {}", enemy.name));
+
+            self.apply_all(&enemy.effects);
+        }
+    }
+
+    /// The players armor class.
+    fn armor_class(&self, defending: bool) -> i32 {
+        let armor_class = BASE_ARMOR_CLASS + self.roll_bonus(Stat::Dex);
+
+        if defending {
+            return armor_class + DEFEND_BONUS;
+        }
+
+        return armor_class;
+    }
+
+    /// The enemy attacks the player.
+    fn enemy_turn(&mut self, combat: &Combat, index: usize) {
+        let enemy = combat.enemies[index].clone();
+        let armor_class = self.armor_class(combat.defending);
+
+        let roll = Dice::new(1, 20).with_modifier(enemy.attack_bonus).roll(&mut self.rng);
+
+        if roll.total < armor_class {
+            self.console.println(&format!("\nThe {} misses you with a roll of: {} ({}) against your AC {}.", enemy.name, roll.total, roll.breakdown(), armor_class));
+            return;
+        }
+
+        let damage = enemy.damage.roll(&mut self.rng);
+
+        self.game_character.damage(damage.total.max(1));
+
+        self.console.println(&format!("\nThe {} hits you with a roll of: {} ({}) against your AC {} for {} damage ({}). HP: {}/{}",
+            enemy.name, roll.total, roll.breakdown(), armor_class, damage.total.max(1), damage.breakdown(), self.game_character.hp, self.game_character.max_hp()));
+    }
+
+    /// Keep what happened to the enemies in the room and tell the player how it ended.
+    fn end_fight(&mut self, combat: Combat) {
+        let room = self.current_room_mut();
+
+        for enemy in &combat.enemies {
+            if enemy.is_defeated() {
+                room.enemies.retain(|other| other.name != enemy.name);
+
+                if let Some(people) = room.npcs.as_mut() {
+                    people.retain(|person| person.name != enemy.name);
+                }
+            } else if let Some(other) = room.enemies.iter_mut().find(|other| other.name == enemy.name) {
+                other.hp = enemy.hp;
+            }
+        }
+
+        if room.npcs.as_ref().map_or(false, |people| people.is_empty()) {
+            room.npcs = None;
+        }
+
+        if !self.active || self.game_character.is_defeated() {
+            return;
+        }
+
+        if combat.fled {
+            self.console.println("=== End of Fight ===");
+
+            let previous_room = self.history.pop().unwrap();
+
+            self.current_room = previous_room;
+
+            self.enter_new_room();
+
+            return;
+        }
+
+        self.console.println("\nYou won the fight!");
+        self.console.println("=== End of Fight ===");
+        self.console.println("\nWhat do you do? (type help for commands)");
+    }
+}
@@ -0,0 +1,202 @@
+use std::vec::Vec;
+use world::actions::Action;
+use crate::Game;
+use crate::game::parser::Command;
+
+/// What a command does once the player has typed it.
+pub type Handler = fn(&mut Game, &Command);
+
+/// What a command expects after its verb.
+///
+/// - Nothing: the verb on its own, for example: inventory.
+/// - Optional: the verb can be followed by what it is about, for example: talk or talk to poet.
+/// - Required: the verb must be followed by what it is about, for example: take key.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Arguments {
+    Nothing,
+    Optional,
+    Required,
+}
+
+/// A command the player can type.
+///
+/// - name: the verb, aliases are other words for it.
+/// - usage: how the command is written in help, for example: take ITEM (from CONTAINER).
+/// - help: what the command does, as shown in help.
+/// - arguments: what the command expects after its verb, checked before the handler is called.
+/// - handler: what the command does.
+#[derive(Clone, Debug)]
+pub struct GameCommand {
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub usage: String,
+    pub help: String,
+    pub arguments: Arguments,
+    pub handler: Handler,
+}
+
+impl GameCommand {
+
+    /// Create a new command with no aliases.
+    pub fn new(name: String, usage: String, help: String, arguments: Arguments, handler: Handler) -> Self {
+        GameCommand {
+            name: name,
+            aliases: Vec::new(),
+            usage: usage,
+            help: help,
+            arguments: arguments,
+            handler: handler,
+        }
+    }
+
+    /// Set the other words the player can type for the command.
+    pub fn with_aliases(mut self, aliases: Vec<String>) -> Self {
+        self.aliases = aliases;
+
+        return self;
+    }
+
+    /// Is the verb the player typed this command?
+    pub fn matches(&self, verb: &str) -> bool {
+        return self.name == verb || self.aliases.iter().any(|alias| alias == verb);
+    }
+
+    /// The line shown for the command in help.
+    pub fn help_line(&self) -> String {
+        if self.aliases.is_empty() {
+            return format!("- {}: {}", self.usage, self.help);
+        }
+
+        return format!("- {}: {} (also: {})", self.usage, self.help, self.aliases.join(", "));
+    }
+}
+
+/// Every command the player can type, in the order they are listed in help.
+///
+/// The game starts with its own commands, see: standard. Adventures can register more, and a
+/// command registered with the verb of an existing command or alias takes it over.
+#[derive(Clone, Debug)]
+pub struct Registry {
+    pub commands: Vec<GameCommand>,
+}
+
+impl Registry {
+
+    /// Create a registry with no commands in it.
+    pub fn new() -> Self {
+        Registry {
+            commands: Vec::new(),
+        }
+    }
+
+    /// Add the command, taking its verbs away from any command that already has them.
+    pub fn register(&mut self, command: GameCommand) {
+        self.commands.retain(|existing| !command.matches(&existing.name));
+
+        for existing in self.commands.iter_mut() {
+            existing.aliases.retain(|alias| !command.matches(alias));
+        }
+
+        self.commands.push(command);
+    }
+
+    /// Find the command for the verb the player typed.
+    pub fn find(&self, verb: &str) -> Option<&GameCommand> {
+        return self.commands.iter().find(|command| command.matches(verb));
+    }
+
+    /// Every verb the player can type, names and aliases.
+    pub fn verbs(&self) -> Vec<&str> {
+        let mut verbs = Vec::new();
+
+        for command in &self.commands {
+            verbs.push(command.name.as_str());
+            verbs.extend(command.aliases.iter().map(|alias| alias.as_str()));
+        }
+
+        return verbs;
+    }
+
+    /// A line for every command, for help.
+    pub fn help(&self) -> Vec<String> {
+        return self.commands.iter().map(|command| command.help_line()).collect();
+    }
+
+    /// The commands every game has.
+    pub fn standard() -> Self {
+        let mut registry = Registry::new();
+
+        let commands = vec![
+            command("help", &[], "help", "see this list.", Arguments::Nothing, |game, _| game.show_help()),
+            command("go", &["walk", "move", "enter", "climb"], "go DIRECTION or EXIT", "move n, s, e, w, ne, nw, se, sw, up, down, in, out or back the way you came, or through an exit by its name: enter house.", Arguments::Optional, |game, command| game.leave_room(&command.object)),
+            command("history", &[], "history", "see the path you took to get here.", Arguments::Nothing, |game, _| game.show_history()),
+            command("look", &["l"], "look (at THING)", "look around, or get a closer look at an item, person, enemy or exit.", Arguments::Optional, |game, command| {
+                if command.has_object() {
+                    game.examine(&command.object);
+                } else {
+                    game.process_action(Action::Look);
+                }
+            }),
+            command("examine", &["x", "inspect"], "examine THING", "get a closer look at an item, person, enemy or exit.", Arguments::Required, |game, command| game.examine(&command.object)),
+            command("explore", &[], "explore", "search the area.", Arguments::Nothing, |game, _| game.process_action(Action::Explore)),
+            command("talk", &["converse", "speak"], "talk (to PERSON)", "talk to the people here.", Arguments::Optional, |game, command| {
+                if command.has_object() {
+                    game.talk_to(&command.object);
+                } else {
+                    game.process_action(Action::Talk);
+                }
+            }),
+            command("take", &["get", "pick up"], "take ITEM (from CONTAINER)", "pick up an item, or take it out of a container.", Arguments::Required, |game, command| {
+                if command.indirect != "" {
+                    game.take_from(&command.object, &command.indirect);
+                } else {
+                    game.take_item(&command.object);
+                }
+            }),
+            command("put", &["place"], "put ITEM in CONTAINER", "put an item you are carrying in a container.", Arguments::Required, |game, command| game.put_item(&command.object, &command.indirect)),
+            command("give", &["offer", "hand"], "give ITEM to PERSON", "give an item you are carrying to someone.", Arguments::Required, |game, command| game.give(&command.object, &command.indirect)),
+            command("drop", &[], "drop ITEM", "drop an item you are carrying.", Arguments::Required, |game, command| game.drop_item(&command.object)),
+            command("inventory", &["inv", "i"], "inventory", "see what you are carrying.", Arguments::Nothing, |game, _| game.show_inventory()),
+            command("hp", &["health"], "hp", "see your hit points. When they run out you are defeated.", Arguments::Nothing, |game, _| game.show_health()),
+            command("sheet", &["character", "level"], "sheet", "see your level, xp, hit points and stats. Checks, conversations, fights and quests give xp.", Arguments::Nothing, |game, _| game.show_sheet()),
+            command("open", &[], "open DOOR", "open a door, by its name or direction.", Arguments::Optional, |game, command| game.open(&command.object)),
+            command("unlock", &[], "unlock DOOR", "unlock a door with its key.", Arguments::Optional, |game, command| game.unlock(&command.object)),
+            command("force", &[], "force DOOR", "fighters can force a door, or something else, open.", Arguments::Optional, |game, command| game.force(&command.object)),
+            command("pick lock", &[], "pick lock DOOR", "thieves can pick the lock on a door, or something else.", Arguments::Optional, |game, command| game.pick_lock(&command.object)),
+            command("cast", &[], "cast SPELL (on PERSON)", "wizards can cast spells.", Arguments::Optional, |game, command| game.cast(&command.rest())),
+            command("steal", &[], "steal (from PERSON)", "thieves can steal.", Arguments::Optional, |game, command| {
+                let target = if command.indirect != "" { &command.indirect } else { &command.object };
+
+                game.class_action(Action::Steal, target);
+            }),
+            command("intimidate", &[], "intimidate PERSON", "fighters can intimidate people.", Arguments::Optional, |game, command| game.class_action(Action::Intimidate, &command.object)),
+            command("attack", &["fight"], "attack (ENEMY)", "start a fight. In a fight you can attack, defend, flee or use your ability.", Arguments::Optional, |game, command| game.start_fight(&command.object)),
+            command("save", &[], "save SLOT", "save your game, where SLOT is any name you like.", Arguments::Optional, |game, command| game.save_game(&slot(command))),
+            command("load", &[], "load SLOT", "pick a saved game back up.", Arguments::Optional, |game, command| game.load_game(&slot(command))),
+            command("quit", &["q", "exit"], "quit", "leave the game.", Arguments::Nothing, |game, _| game.quit_game()),
+        ];
+
+        for command in commands {
+            registry.register(command);
+        }
+
+        return registry;
+    }
+}
+
+/// The default registry is the one every game starts with, see: standard.
+impl Default for Registry {
+    fn default() -> Self {
+        return Registry::standard();
+    }
+}
+
+fn command(name: &str, aliases: &[&str], usage: &str, help: &str, arguments: Arguments, handler: Handler) -> GameCommand {
+    return GameCommand::new(name.to_string(), usage.to_string(), help.to_string(), arguments, handler)
+        .with_aliases(aliases.iter().map(|alias| alias.to_string()).collect());
+}
+
+/// Slots are a single word, such as: river.
+fn slot(command: &Command) -> String {
+    return command.object.split_whitespace().next().unwrap_or("").to_string();
+}
@@ -0,0 +1,109 @@
+use std::vec::Vec;
+use character::charactersheet::MAX_STAT;
+use world::level::Level;
+use crate::Game;
+
+impl Game {
+
+    /// Give the player experience, then level them up for every level it gets them to.
+    ///
+    /// The reason is shown to the player, for example: "defeating the Wolf".
+    pub(crate) fn gain_xp(&mut self, amount: i32, reason: &str) {
+        if amount <= 0 {
+            return;
+        }
+
+        self.game_character.xp = self.game_character.xp + amount;
+
+        self.console.println(&format!("You gain {} XP for {}. XP: {}", amount, reason, self.progress()));
+
+        while let Some(level) = self.world.next_level(self.game_character.level).cloned() {
+            if self.game_character.xp < level.xp {
+                break;
+            }
+
+            self.level_up(level);
+        }
+    }
+
+    /// Raise the players level, giving them what the level table says they get.
+    ///
+    /// The max hp they gain is healed as well, so leveling up never leaves them more hurt.
+    fn level_up(&mut self, level: Level) {
+        let character = &mut self.game_character;
+
+        character.level = level.level;
+        character.bonus_hp = character.bonus_hp + level.max_hp;
+        character.heal(level.max_hp);
+
+        let mut gains = Vec::new();
+
+        if level.max_hp > 0 {
+            gains.push(format!("max hp {:+}", level.max_hp));
+        }
+
+        if let Some(stats) = character.stats.as_mut() {
+            for (stat, amount) in &level.stats {
+                stats.set(*stat, (stats.get(*stat) + amount).max(1).min(MAX_STAT));
+
+                gains.push(format!("{} {:+}", stat.name(), amount));
+            }
+        }
+
+        self.console.println(&format!("\nLevel up! You are now level {}.", level.level));
+
+        if !gains.is_empty() {
+            self.console.println(&format!("You gain: {}", gains.join(", ")));
+        }
+
+        if !level.unlocks.is_empty() {
+            self.console.println(&format!("You can now use: {}", level.unlocks.join(", ")));
+        }
+    }
+
+    /// Has the player reached the level the ability unlocks at? When they haven't we tell them.
+    ///
+    /// Abilities the level table doesn't mention are always unlocked.
+    pub(crate) fn has_unlocked(&mut self, ability: &str) -> bool {
+        match self.world.unlocked_at(ability) {
+            Some(level) if self.game_character.level < level => {
+                self.console.println(&format!("You need to be level {} to use {}.", level, ability));
+
+                return false;
+            },
+            _ => return true,
+        }
+    }
+
+    /// The players xp and how far they are from the next level.
+    fn progress(&self) -> String {
+        match self.world.next_level(self.game_character.level) {
+            Some(next) => format!("{}/{} to level {}", self.game_character.xp, next.xp, next.level),
+            None => format!("{} (max level)", self.game_character.xp),
+        }
+    }
+
+    /// Show the character sheet: who the player is, their level, hit points and stats.
+    pub(crate) fn show_sheet(&mut self) {
+        let character = self.game_character.clone();
+
+        let race = character.race.as_ref().map_or("none".to_string(), |race| race.name.clone());
+        let class = character.class.as_ref().map_or("none".to_string(), |class| class.name.clone());
+
+        self.console.println("\n-------------------");
+        self.console.println(&format!("{}, {} {}", character.name, race, class));
+        self.console.println(&format!("Level: {}", character.level));
+        self.console.println(&format!("XP: {}", self.progress()));
+        self.console.println(&format!("HP: {}/{}", character.hp, character.max_hp()));
+
+        if let Some(stats) = &character.stats {
+            self.console.println(&format!("Stats: {}", stats.summary()));
+        }
+
+        if let Some(race) = &character.race {
+            self.console.println(&format!("Traits: {}", race.trait_summary()));
+        }
+
+        self.console.println("-------------------");
+    }
+}
@@ -0,0 +1,97 @@
+use std::vec::Vec;
+
+/// Words that are skipped where ever they are, such as: the, a and please.
+pub const FILLER_WORDS: [&str; 8] = ["the", "a", "an", "some", "please", "my", "this", "that"];
+
+/// Words that join the object of a command to a second object, such as: to in give letter to poet.
+pub const PREPOSITIONS: [&str; 10] = ["at", "to", "from", "in", "into", "inside", "on", "onto", "with", "about"];
+
+/// Verbs that are two words long.
+const TWO_WORD_VERBS: [&str; 2] = ["pick lock", "pick up"];
+
+/// What the player typed, broken into its parts.
+///
+/// - verb: what the player wants to do, for example: give.
+/// - object: what they want to do it to, for example: letter.
+/// - preposition: the word joining the object to the indirect object, for example: to.
+/// - indirect: the second thing the command is about, for example: poet.
+///
+/// Filler words are dropped, so "give the letter to the poet" and "give letter to poet"
+/// are the same command. A preposition straight after the verb is dropped too, so
+/// "look at the old man" has the object: old man, and "take key from chest" has the object:
+/// key and the indirect object: chest.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Command {
+    pub verb: String,
+    pub object: String,
+    pub preposition: Option<String>,
+    pub indirect: String,
+}
+
+impl Command {
+
+    /// Is the object the player typed empty?
+    pub fn has_object(&self) -> bool {
+        return self.object != "";
+    }
+
+    /// The object and the indirect object joined back up, for commands that take everything
+    /// after the verb, for example: cast charm on poet.
+    pub fn rest(&self) -> String {
+        match &self.preposition {
+            Some(preposition) => format!("{} {} {}", self.object, preposition, self.indirect).trim().to_string(),
+            None => self.object.clone(),
+        }
+    }
+}
+
+/// Break what the player typed into a command.
+///
+/// Everything is lower cased. Returns None when there is nothing but filler.
+pub fn parse(input: &str) -> Option<Command> {
+    let input = input.to_lowercase();
+
+    let mut words: Vec<&str> = input.split_whitespace()
+        .filter(|word| !FILLER_WORDS.contains(word))
+        .collect();
+
+    if words.is_empty() {
+        return None;
+    }
+
+    let mut verb = words.remove(0).to_string();
+
+    if let Some(next) = words.first() {
+        let two_words = format!("{} {}", verb, next);
+
+        if TWO_WORD_VERBS.contains(&two_words.as_str()) {
+            verb = two_words;
+            words.remove(0);
+        }
+    }
+
+    // A preposition straight after the verb belongs to the verb, as long as something follows it.
+    // On its own it is the object, for example: go in.
+    if words.len() > 1 && PREPOSITIONS.contains(&words[0]) {
+        words.remove(0);
+    }
+
+    let split = words.iter().skip(1).position(|word| PREPOSITIONS.contains(word)).map(|index| index + 1);
+
+    let command = match split {
+        Some(index) => Command {
+            verb: verb,
+            object: words[..index].join(" "),
+            preposition: Some(words[index].to_string()),
+            indirect: words[index + 1..].join(" "),
+        },
+        None => Command {
+            verb: verb,
+            object: words.join(" "),
+            preposition: None,
+            indirect: String::new(),
+        },
+    };
+
+    return Some(command);
+}
@@ -0,0 +1,306 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::vec::Vec;
+use serde::{Serialize, Deserialize};
+use character::charactersheet::{Character, MAX_STAT};
+use core::rng::{self, Rng};
+use core::stats::Stat;
+use world::World;
+use world::item::Item;
+use world::state::State;
+use crate::Game;
+
+/// The version of the save format.
+///
+/// Bump this when the shape of a save changes. New fields should be given a
+/// `#[serde(default)]` so saves made before them keep loading.
+pub const SAVE_VERSION: u32 = 6;
+
+/// Where saves are written, relative to where the game is run from, unless the game is given
+/// another directory, see Game::with_save_directory.
+pub const SAVE_DIRECTORY: &str = "saves";
+
+/// Why a save could not be written or read.
+#[derive(Debug)]
+pub struct SaveError {
+    pub slot: String,
+    pub message: String,
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Save {}: {}", self.slot, self.message)
+    }
+}
+
+impl std::error::Error for SaveError {}
+
+/// Everything needed to pick an adventure back up where the player left it.
+///
+/// The world itself is not saved, it comes from the adventure. Instead we keep what the player
+/// changed in each room: the items in it, the state of its doors and how its people feel about
+/// the player. When a save is loaded after the adventure has been updated, rooms, doors and
+/// people that no longer exist are skipped.
+///
+/// The seed the game was started with is kept, along with where the rng was up to, so the
+/// rolls after loading are the same as they would have been. Saves from before version 2
+/// don't have them and keep rolling with the rng the game already has.
+///
+/// Characters in saves from before version 3 had no hit points, they are loaded at full hp.
+///
+/// From version 4 each room keeps the enemies left in it and how hurt they are. People that
+/// aren't in the save any more were defeated in a fight and are removed.
+///
+/// From version 5 the character has a level and xp. Characters from older saves are level 1.
+///
+/// From version 6 what has already given xp is kept in awarded, apart from the story state.
+/// Older saves kept it in flags starting with xp_. Those are dropped from the state, so xp
+/// from before the save can be earned again.
+///
+/// Stats are kept from 1 to MAX_STAT when a save is loaded, in case it was edited by hand.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct Save {
+    pub version: u32,
+    pub character: Character,
+    pub current_room: String,
+    #[serde(default)]
+    pub history: Vec<String>,
+    #[serde(default)]
+    pub state: State,
+    #[serde(default)]
+    pub awarded: BTreeSet<String>,
+    #[serde(default)]
+    pub rooms: Vec<RoomSave>,
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub rng_state: Option<u64>,
+}
+
+/// What the player changed in a room.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct RoomSave {
+    pub id: String,
+    #[serde(default)]
+    pub items: Vec<Item>,
+    #[serde(default)]
+    pub doors: Vec<DoorSave>,
+    #[serde(default)]
+    pub people: Vec<PersonSave>,
+    #[serde(default)]
+    pub enemies: Option<Vec<EnemySave>>,
+}
+
+/// A door, found by the direction of the exit it is on, or the name of the exit when it isn't in a direction.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct DoorSave {
+    pub direction: String,
+    pub closed: bool,
+    pub locked: bool,
+}
+
+/// An enemy that hasn't been defeated yet.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct EnemySave {
+    pub name: String,
+    pub hp: i32,
+}
+
+/// How someone feels about the player.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct PersonSave {
+    pub name: String,
+    pub disposition: i32,
+}
+
+impl Save {
+
+    /// Take a save of the game as it is now.
+    pub fn from_game(game: &Game) -> Self {
+        let mut rooms: Vec<RoomSave> = game.world.rooms.values().map(|room| RoomSave {
+            id: room.id.clone(),
+            items: room.items.clone(),
+            doors: room.exits.iter().filter_map(|exit| exit.door.as_ref().map(|door| DoorSave {
+                direction: exit.word(),
+                closed: door.closed,
+                locked: door.locked,
+            })).collect(),
+            people: room.npcs.iter().flatten().map(|person| PersonSave {
+                name: person.name.clone(),
+                disposition: person.disposition,
+            }).collect(),
+            enemies: Some(room.enemies.iter().map(|enemy| EnemySave {
+                name: enemy.name.clone(),
+                hp: enemy.hp,
+            }).collect()),
+        }).collect();
+
+        // Keep the file the same from one save to the next.
+        rooms.sort_by(|a, b| a.id.cmp(&b.id));
+
+        return Save {
+            version: SAVE_VERSION,
+            character: game.game_character.clone(),
+            current_room: game.current_room.clone(),
+            history: game.history.clone(),
+            state: game.state.clone(),
+            awarded: game.awarded.clone(),
+            rooms: rooms,
+            seed: Some(game.rng.seed()),
+            rng_state: Some(game.rng.state()),
+        };
+    }
+
+    /// Put the game back the way it was when the save was taken.
+    ///
+    /// The world starts again from the adventure, then what the player changed is put back
+    /// on top of it, so enemies defeated after the save was taken are back.
+    ///
+    /// If the room the player saved in is no longer in the adventure they start again
+    /// from the adventures first room, keeping their character and the story so far.
+    pub fn restore(self, game: &mut Game) {
+        game.world = game.adventure.clone();
+
+        apply_rooms(&mut game.world, &self.rooms);
+
+        game.game_character = self.character;
+        game.state = self.state;
+        game.awarded = self.awarded;
+
+        if self.version < 6 {
+            game.state.values.retain(|key, _| !key.starts_with("xp_"));
+        }
+
+        if self.version < 3 {
+            game.game_character.hp = game.game_character.max_hp();
+        }
+
+        // A save can be edited by hand, keep the stats to what a character could have.
+        if let Some(stats) = game.game_character.stats.as_mut() {
+            for stat in Stat::all() {
+                stats.set(stat, stats.get(stat).max(1).min(MAX_STAT));
+            }
+        }
+
+        if let (Some(seed), Some(state)) = (self.seed, self.rng_state) {
+            game.rng = Rng::from_state(seed, state);
+        }
+
+        rng::report(&game.rng);
+
+        if game.world.rooms.contains_key(&self.current_room) {
+            game.current_room = self.current_room;
+            game.history = self.history.into_iter().filter(|room| game.world.rooms.contains_key(room)).collect();
+        } else {
+            game.console.println("The place you saved in is no longer part of this adventure. You find yourself back at the start.");
+
+            game.current_room = game.world.start.clone();
+            game.history = Vec::new();
+        }
+    }
+}
+
+fn apply_rooms(world: &mut World, rooms: &Vec<RoomSave>) {
+    for saved in rooms {
+        let room = match world.rooms.get_mut(&saved.id) {
+            Some(room) => room,
+            None => continue,
+        };
+
+        room.items = saved.items.clone();
+
+        for saved_door in &saved.doors {
+            let door = room.exits.iter_mut()
+                .find(|exit| exit.word() == saved_door.direction)
+                .and_then(|exit| exit.door.as_mut());
+
+            if let Some(door) = door {
+                door.closed = saved_door.closed;
+                door.locked = saved_door.locked;
+            }
+        }
+
+        if let Some(enemies) = &saved.enemies {
+            let defeated: Vec<String> = room.enemies.iter()
+                .filter(|enemy| !enemies.iter().any(|saved_enemy| saved_enemy.name == enemy.name))
+                .map(|enemy| enemy.name.clone())
+                .collect();
+
+            room.enemies.retain(|enemy| !defeated.contains(&enemy.name));
+
+            for enemy in room.enemies.iter_mut() {
+                if let Some(saved_enemy) = enemies.iter().find(|saved_enemy| saved_enemy.name == enemy.name) {
+                    enemy.hp = saved_enemy.hp.min(enemy.max_hp);
+                }
+            }
+
+            if let Some(people) = room.npcs.as_mut() {
+                people.retain(|person| !defeated.contains(&person.name));
+            }
+
+            if room.npcs.as_ref().map_or(false, |people| people.is_empty()) {
+                room.npcs = None;
+            }
+        }
+
+        for saved_person in &saved.people {
+            let person = room.npcs.iter_mut().flatten().find(|person| person.name == saved_person.name);
+
+            if let Some(person) = person {
+                person.disposition = saved_person.disposition;
+            }
+        }
+    }
+}
+
+/// The file a slot is saved to in the directory.
+///
+/// Slot names can only use letters, numbers, - and _ so a save can't be written outside
+/// of the save directory.
+pub fn slot_path(directory: &Path, slot: &str) -> Result<PathBuf, SaveError> {
+    if slot.is_empty() || !slot.chars().all(|character| character.is_ascii_alphanumeric() || character == '-' || character == '_') {
+        return Err(error(slot, "slot names can only use letters, numbers, - and _.".to_string()));
+    }
+
+    return Ok(directory.join(format!("{}.json", slot)));
+}
+
+/// Write the save to the slot in the directory, replacing what was there.
+pub fn write_save(directory: &Path, slot: &str, save: &Save) -> Result<PathBuf, SaveError> {
+    let path = slot_path(directory, slot)?;
+
+    fs::create_dir_all(directory).map_err(|e| error(slot, e.to_string()))?;
+
+    let contents = serde_json::to_string_pretty(save).map_err(|e| error(slot, e.to_string()))?;
+
+    fs::write(&path, contents).map_err(|e| error(slot, e.to_string()))?;
+
+    return Ok(path);
+}
+
+/// Read the save in the slot in the directory.
+pub fn read_save(directory: &Path, slot: &str) -> Result<Save, SaveError> {
+    let path = slot_path(directory, slot)?;
+
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(_) => return Err(error(slot, "there is no save in this slot.".to_string())),
+    };
+
+    let save: Save = serde_json::from_str(&contents).map_err(|e| error(slot, format!("the save is damaged: {}", e)))?;
+
+    if save.version > SAVE_VERSION {
+        return Err(error(slot, format!("the save was made by a newer version of the game (version {}).", save.version)));
+    }
+
+    return Ok(save);
+}
+
+fn error(slot: &str, message: String) -> SaveError {
+    SaveError {
+        slot: slot.to_string(),
+        message: message,
+    }
+}
@@ -0,0 +1,274 @@
+use std::vec::Vec;
+use core::text_handeling::{edit_distance, is_close, or_list};
+use world::item::Item;
+use world::person::Person;
+use world::enemy::Enemy;
+use world::room::{Direction, Exit};
+use crate::Game;
+
+/// What a name the player typed refers to, in the current room or in their inventory.
+#[derive(Clone, Debug)]
+pub enum Target {
+
+    /// An item the player is carrying.
+    Carried(Item),
+
+    /// An item in the room.
+    Item(Item),
+
+    /// A person in the room.
+    Person(Person),
+
+    /// An enemy in the room.
+    Enemy(Enemy),
+
+    /// An exit out of the room, named by its direction, its name or its door.
+    Exit(Exit),
+
+    /// Nothing the player can see.
+    Nothing,
+}
+
+impl Target {
+
+    /// Is it an item, carried or in the room?
+    pub fn is_item(&self) -> bool {
+        return self.is_carried() || self.is_room_item();
+    }
+
+    /// Is it an item the player is carrying?
+    pub fn is_carried(&self) -> bool {
+        return matches!(self, Target::Carried(_));
+    }
+
+    /// Is it an item in the room?
+    pub fn is_room_item(&self) -> bool {
+        return matches!(self, Target::Item(_));
+    }
+
+    /// Is it a person in the room?
+    pub fn is_person(&self) -> bool {
+        return matches!(self, Target::Person(_));
+    }
+
+    /// Is it an enemy in the room?
+    pub fn is_enemy(&self) -> bool {
+        return matches!(self, Target::Enemy(_));
+    }
+}
+
+/// The fewest letters that can be matched against part of a name, so "a" doesn't match
+/// everything with an a in it.
+pub(crate) const MIN_PARTIAL: usize = 3;
+
+/// Something the player could be talking about.
+///
+/// The label is how it is shown to the player and the words are what they can call it, lower
+/// cased: its name and aliases.
+struct Candidate {
+    label: String,
+    words: Vec<String>,
+    direction: Option<Direction>,
+    target: Target,
+}
+
+impl Candidate {
+
+    /// The closest any of the words, or any part of them, is to what the player typed, as long
+    /// as it is close enough to be a typo.
+    fn distance(&self, name: &str) -> Option<usize> {
+        return self.words.iter()
+            .flat_map(|word| std::iter::once(word.as_str()).chain(word.split_whitespace()))
+            .filter(|word| is_close(name, word))
+            .map(|word| edit_distance(name, word))
+            .min();
+    }
+}
+
+impl Game {
+
+    /// Work out what the name refers to.
+    ///
+    /// A name or alias spelled out in full wins, with what the player is carrying coming first,
+    /// then the items, people, enemies and exits of the room. Other wise part of a name will do,
+    /// as long as it is at least three letters, and failing that a name with a typo in it. When that leaves more than one thing the player
+    /// is asked which one they mean.
+    ///
+    /// Items in a dark room can't be seen with out darkvision, and hidden exits can't be
+    /// named at all.
+    pub(crate) fn resolve(&mut self, name: &str) -> Target {
+        return self.resolve_fitting(name, |_| true);
+    }
+
+    /// Work out what the name refers to, out of the things that fit, for example only the
+    /// items in the room for take. Things that don't fit are never offered to the player.
+    pub(crate) fn resolve_fitting(&mut self, name: &str, fits: impl Fn(&Target) -> bool) -> Target {
+        let name = name.to_lowercase();
+
+        if name == "" {
+            return Target::Nothing;
+        }
+
+        let candidates: Vec<Candidate> = self.candidates().into_iter().filter(|candidate| fits(&candidate.target)).collect();
+        let direction = Direction::from_word(&name);
+
+        let exact = candidates.iter().find(|candidate| {
+            candidate.words.contains(&name) || (direction.is_some() && candidate.direction == direction)
+        });
+
+        if let Some(candidate) = exact {
+            return candidate.target.clone();
+        }
+
+        let partial: Vec<&Candidate> = candidates.iter()
+            .filter(|candidate| name.chars().count() >= MIN_PARTIAL && candidate.words.iter().any(|word| word.contains(&name)))
+            .collect();
+
+        if !partial.is_empty() {
+            return self.choose(partial);
+        }
+
+        let best = candidates.iter().filter_map(|candidate| candidate.distance(&name)).min();
+
+        let close: Vec<&Candidate> = candidates.iter().filter(|candidate| best.is_some() && candidate.distance(&name) == best).collect();
+
+        return self.choose(close);
+    }
+
+    /// Is the name, spelled out in full, the name of something the player can see?
+    pub(crate) fn is_named(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+
+        return self.candidates().iter().any(|candidate| candidate.words.contains(&name));
+    }
+
+    /// The full name of what the player is talking about, lower cased, so it can be looked up
+    /// by name. When it isn't anything the name is given back as it is.
+    pub(crate) fn target_name(&mut self, name: &str) -> String {
+        return self.target_name_fitting(name, |_| true);
+    }
+
+    /// The full name of what the player is talking about, out of the things that fit.
+    pub(crate) fn target_name_fitting(&mut self, name: &str, fits: impl Fn(&Target) -> bool) -> String {
+        match self.resolve_fitting(name, fits) {
+            Target::Carried(item) | Target::Item(item) => item.name.to_lowercase(),
+            Target::Person(person) => person.name.to_lowercase(),
+            Target::Enemy(enemy) => enemy.name.to_lowercase(),
+            Target::Exit(exit) => match &exit.door {
+                Some(door) => door.name.to_lowercase(),
+                None => exit.word(),
+            },
+            Target::Nothing => name.to_string(),
+        }
+    }
+
+    /// Everything the player could be talking about, in the order they are picked in.
+    fn candidates(&self) -> Vec<Candidate> {
+        let room = self.current_room().clone();
+
+        let mut candidates = Vec::new();
+
+        for item in &self.game_character.inventory {
+            candidates.push(item_candidate(item, Target::Carried(item.clone())));
+        }
+
+        if self.can_see() {
+            for item in &room.items {
+                candidates.push(item_candidate(item, Target::Item(item.clone())));
+            }
+        }
+
+        for person in room.npcs.iter().flatten() {
+            candidates.push(Candidate {
+                label: person.name.clone(),
+                words: vec![person.name.to_lowercase()],
+                direction: None,
+                target: Target::Person(person.clone()),
+            });
+        }
+
+        for enemy in &room.enemies {
+            candidates.push(Candidate {
+                label: enemy.name.clone(),
+                words: vec![enemy.name.to_lowercase()],
+                direction: None,
+                target: Target::Enemy(enemy.clone()),
+            });
+        }
+
+        for exit in room.exits.iter().filter(|exit| !exit.hidden) {
+            let mut words = vec![exit.word()];
+
+            words.extend(exit.name.iter().chain(exit.door.as_ref().map(|door| &door.name)).map(|name| name.to_lowercase()));
+
+            candidates.push(Candidate {
+                label: match &exit.door {
+                    Some(door) => door.name.clone(),
+                    None => exit.word(),
+                },
+                words: words,
+                direction: Some(exit.direction.clone()).filter(|direction| *direction != Direction::NONE),
+                target: Target::Exit(exit.clone()),
+            });
+        }
+
+        return candidates;
+    }
+
+    /// Pick from the things the player could mean. When there is more than one we ask which,
+    /// until they answer with one of them.
+    ///
+    /// Things with the same name, such as a bandit that is both a person and an enemy, are
+    /// counted once.
+    fn choose(&mut self, candidates: Vec<&Candidate>) -> Target {
+        let mut options: Vec<&Candidate> = Vec::new();
+
+        for candidate in candidates {
+            if !options.iter().any(|option| option.label.to_lowercase() == candidate.label.to_lowercase()) {
+                options.push(candidate);
+            }
+        }
+
+        match options.len() {
+            0 => return Target::Nothing,
+            1 => return options[0].target.clone(),
+            _ => {},
+        }
+
+        let labels: Vec<String> = options.iter().map(|option| format!("the {}", option.label)).collect();
+        let question = format!("Which one: {}?", or_list(&labels));
+
+        self.console.println(&question);
+
+        loop {
+            let answer = match self.console.prompt() {
+                Some(answer) => answer.trim().to_lowercase(),
+                None => return Target::Nothing,
+            };
+
+            let answer = answer.strip_prefix("the ").unwrap_or(&answer).to_string();
+
+            let exact = options.iter().find(|option| option.label.to_lowercase() == answer);
+            let picked: Vec<&&Candidate> = options.iter().filter(|option| answer != "" && option.label.to_lowercase().contains(&answer)).collect();
+
+            match (exact, picked.len()) {
+                (Some(option), _) => return option.target.clone(),
+                (None, 1) => return picked[0].target.clone(),
+                _ => self.console.println(&question),
+            }
+        }
+    }
+}
+
+fn item_candidate(item: &Item, target: Target) -> Candidate {
+    let mut words = vec![item.name.to_lowercase()];
+
+    words.extend(item.aliases.iter().map(|alias| alias.to_lowercase()));
+
+    return Candidate {
+        label: item.name.clone(),
+        words: words,
+        direction: None,
+        target: target,
+    };
+}
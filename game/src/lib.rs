@@ -1,17 +1,34 @@
 mod menu_system;
+mod game;
 
-use std::io;
-use std::io::Write;
-use std::process;
-use std::collections::HashMap;
+pub use crate::game::save;
+pub use crate::game::parser;
+pub use crate::game::commands;
+
+use std::collections::{BTreeSet, HashMap};
+use std::convert::TryFrom;
+use std::path::PathBuf;
 use character::charactersheet::{build_character, Character};
-use core::text_handeling::unwrap_str;
+use core::console::Console;
+use core::rng::{self, Rng};
+use core::dice::Dice;
+use core::text_handeling::{unwrap_str, closest, or_list};
+use core::stat_bonus::create_all_stat_bonuses;
+use core::stats::Stat;
 use world::World;
-use world::room::{Room, Direction};
+use world::room::{Room, Direction, Exit, Door};
+use world::condition::Condition;
+use world::effect::Effect;
+use world::context::Context;
+use world::state::{State, Value};
 use world::actions::Action;
 use world::person::Person;
 use menu::menu_system::{display_menu};
 use crate::menu_system::talk_menu::{menu_choices, process};
+use crate::game::save::{Save, write_save, read_save, SAVE_DIRECTORY};
+use crate::game::parser::{parse, Command};
+use crate::game::target::Target;
+use crate::game::commands::{Registry, GameCommand, Arguments};
 
 /// Core Game Struct
 ///
@@ -20,18 +37,106 @@ use crate::menu_system::talk_menu::{menu_choices, process};
 /// This is the most integral part of the game as its the entry to the main game loop.
 /// Each adventure would have its own World struct passed into the game that is then acted upon to
 /// move the player through the world passed in.
+///
+/// The game owns the world, so anything that changes in a room stays changed. The current room
+/// is the id of a room in that world.
+///
+/// History is the trail of rooms the player walked through to get to the current room, oldest
+/// first. Going back pops the last room off of it, so a player can walk back to the start.
+///
+/// The state is the story so far: flags and variables set by actions, choices and exits.
+/// Awarded is kept apart from it, it names everything that has already given the player xp.
+///
+/// Everything the player types and reads goes through the console, and every roll comes
+/// from the rng so a game can be replayed from its seed.
+///
+/// Start is the game as it was when it started running, so a defeated player can restart.
+/// Adventure is the world before the player changed anything, saves are loaded on top of it.
+///
+/// Pending fight is the names of the enemies that will fight the player once what they typed
+/// has been done, for example because they walked into a room with hostile enemies.
+///
+/// Commands are everything the player can type: the games own, then the ones the adventure adds.
+///
+/// Saves are written to and read from the save directory.
 #[derive(Debug)]
 pub struct Game {
     pub active: bool,
     pub game_character: Character,
     pub stat_bonuses: Vec<i32>,
-    pub current_room: Option<Room>,
-    pub previous_room: Option<Room>
+    pub world: World,
+    pub adventure: World,
+    pub current_room: String,
+    pub history: Vec<String>,
+    pub state: State,
+    pub awarded: BTreeSet<String>,
+    pub console: Box<dyn Console>,
+    pub rng: Rng,
+    pub start: Option<Save>,
+    pub pending_fight: Vec<String>,
+    pub commands: Registry,
+    pub save_directory: PathBuf,
 }
 
 /// Game implementation.
 impl Game {
 
+    /// Create a new game for the character that starts in the worlds base room.
+    pub fn new(character: Character, world: World, console: Box<dyn Console>) -> Self {
+        let mut commands = Registry::standard();
+
+        for verb in &world.verbs {
+            commands.register(GameCommand::new(verb.name.clone(), verb.usage.clone(), verb.help.clone(), Arguments::Optional, |game, command| game.adventure_command(command))
+                .with_aliases(verb.aliases.clone()));
+        }
+
+        Game {
+            active: true,
+            game_character: character,
+            stat_bonuses: create_all_stat_bonuses(),
+            current_room: world.start.clone(),
+            history: Vec::new(),
+            state: State::new(),
+            awarded: BTreeSet::new(),
+            adventure: world.clone(),
+            world: world,
+            console: console,
+            rng: Rng::from_time(),
+            start: None,
+            pending_fight: Vec::new(),
+            commands: commands,
+            save_directory: PathBuf::from(SAVE_DIRECTORY),
+        }
+    }
+
+    /// Add a command to the game, or take over the verb of one the game already has.
+    pub fn with_command(mut self, command: GameCommand) -> Self {
+        self.commands.register(command);
+
+        return self;
+    }
+
+    /// Roll with a generator made from the seed, so the game plays out the same each time.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Rng::new(seed);
+
+        return self;
+    }
+
+    /// Roll with the generator, for example the one the character was created with.
+    pub fn with_rng(mut self, rng: Rng) -> Self {
+        self.rng = rng;
+
+        return self;
+    }
+
+    /// Write and read saves in the directory, instead of the saves directory where the game is run from.
+    pub fn with_save_directory(mut self, directory: PathBuf) -> Self {
+        self.save_directory = directory;
+
+        return self;
+    }
+
     /// Is game active?
     pub fn is_active(&self) -> bool {
         return self.active;
@@ -43,107 +148,214 @@ impl Game {
     }
 
     /// Run the loop.
-    pub fn run(&mut self, world: World) {
+    ///
+    /// Starts by describing the room the player is in. Fights start between commands. When the
+    /// player is defeated the loop stops until they restart, load a save or quit.
+    pub fn run(&mut self) {
 
-        self.set_base_room(&world);
+        self.start = Some(Save::from_game(self));
+
+        self.enter_new_room();
 
         while self.active {
-            print!("> ");
+            rng::report(&self.rng);
+
+            while self.active && !self.pending_fight.is_empty() && !self.game_character.is_defeated() {
+                let names = std::mem::take(&mut self.pending_fight);
 
-            io::stdout().flush().expect("Error flushing stdout!");
+                self.fight(names);
+            }
 
-            let mut input = String::new();
+            if self.active && self.game_character.is_defeated() {
+                self.pending_fight.clear();
 
-            io::stdin().read_line(&mut input)
-                       .expect("Error reading stdin!");
+                self.defeat();
+
+                continue;
+            }
+
+            if !self.active {
+                return;
+            }
+
+            let input = match self.console.prompt() {
+                Some(input) => input,
+                None => {
+                    self.quit_game();
+                    return;
+                },
+            };
 
             self.parse_input(input);
         }
     }
 
-    fn set_base_room(&mut self, world: &World) {
+    /// The player has been defeated. They can restart the adventure, load a save or quit.
+    fn defeat(&mut self) {
+        self.console.println("\n===================");
+        self.console.println("You have been defeated.");
+        self.console.println("===================");
+
+        loop {
+            self.console.println("\nType restart to start the adventure again, load SLOT to load a save or quit.");
+
+            let input = match self.console.prompt() {
+                Some(input) => input.trim().to_lowercase(),
+                None => {
+                    self.quit_game();
+                    return;
+                },
+            };
 
-        let room = world.room.clone();
+            let words: Vec<&str> = input.split_whitespace().collect();
 
-        self.current_room = Some(room);
+            match words.as_slice() {
+                ["restart"] => {
+                    self.restart();
+                    return;
+                },
+                ["load", slot] => {
+                    self.load_game(slot);
+
+                    if !self.game_character.is_defeated() {
+                        return;
+                    }
+                },
+                ["q"] | ["quit"] | ["exit"] => {
+                    self.quit_game();
+                    return;
+                },
+                _ => {},
+            }
+        }
     }
 
-    fn set_current_room(&mut self, room: Room) {
-        self.current_room = Some(room);
+    /// Put the game back the way it was when it started. The rng carries on, so the
+    /// rolls are not the same as the first time.
+    fn restart(&mut self) {
+        let start = match self.start.clone() {
+            Some(start) => start,
+            None => return,
+        };
+
+        let rng = self.rng.clone();
+
+        start.restore(self);
+
+        self.rng = rng;
+
+        self.console.println("\nYou start the adventure again.");
+
+        self.enter_new_room();
     }
 
-    fn set_previous_room(&mut self, room: Room) {
-        self.previous_room = Some(room);
+    /// The room the player is in.
+    pub fn current_room(&self) -> &Room {
+        return self.world.room(&self.current_room);
     }
 
-    fn parse_input(&mut self, input: String) {
-        let words: Vec<&str> = input.split_whitespace().collect();
+    /// The room the player is in, so it can be changed.
+    ///
+    /// Changes are made to the room in the world, so they are still there when the player
+    /// leaves and comes back.
+    pub fn current_room_mut(&mut self) -> &mut Room {
+        return self.world.room_mut(&self.current_room);
+    }
 
-        if words.is_empty() {
-            println!("Invalid input.");
+    fn set_current_room(&mut self, room: String) {
+        self.current_room = room;
+    }
+
+    /// Break what the player typed into a command and do it.
+    fn parse_input(&mut self, input: String) {
+        match parse(&input) {
+            Some(command) => self.parse_commands(command),
+            None => self.console.println("Invalid input."),
         }
+    }
 
-        let mut result: Vec<String> = Vec::new();
+    /// Find the command for the verb, check what the player typed after it and do it.
+    fn parse_commands(&mut self, command: Command) {
+        let registered = match self.commands.find(&command.verb) {
+            Some(registered) => registered.clone(),
+            None => {
+                let suggestions: Vec<String> = closest(&command.verb, &self.commands.verbs()).iter().map(|verb| verb.to_string()).collect();
 
-        for word in words {
-            let single_word = self.clean_words(word.to_string());
+                if suggestions.is_empty() {
+                    self.console.println(&format!("What is: {}?", command.verb));
+                } else {
+                    self.console.println(&format!("What is: {}? Did you mean: {}?", command.verb, or_list(&suggestions)));
+                }
 
-            result.push(single_word);
-        }
+                return;
+            },
+        };
 
-        self.parse_commands(result);
+        match registered.arguments {
+            Arguments::Nothing if command.has_object() => {
+                self.console.println(&format!("Just type: {}", command.verb));
+                return;
+            },
+            Arguments::Required if !command.has_object() => {
+                let mut letters = command.verb.chars();
+
+                let verb = match letters.next() {
+                    Some(first) => format!("{}{}", first.to_uppercase(), letters.as_str()),
+                    None => String::new(),
+                };
+
+                self.console.println(&format!("{} what?", verb));
+                return;
+            },
+            _ => (registered.handler)(self, &command),
+        }
     }
 
-    fn clean_words(&mut self, word: String) -> String {
-        let mut s = String::new();
+    /// List every command, from the registry.
+    fn show_help(&mut self) {
+        self.console.println("\n-------------------");
 
-        for character in word.chars() {
-            for d in character.to_lowercase() {
-                s.push(d);
-            }
+        for line in self.commands.help() {
+            self.console.println(&line);
         }
 
-        return s;
+        self.console.println("-------------------");
     }
 
-    fn parse_commands(&mut self, words: Vec<String>) {
-        let mut words = words.iter();
-
-        let command = unwrap_str(words.next());
+    /// Save the game to the slot.
+    fn save_game(&mut self, slot: &str) {
+        if slot == "" {
+            self.console.println("Save to where? Type: save SLOT, for example: save river.");
+            return;
+        }
 
-        if command == "" {
-            println!("Invalid input.");
+        match write_save(&self.save_directory, slot, &Save::from_game(self)) {
+            Ok(_) => self.console.println(&format!("Game saved to: {}.", slot)),
+            Err(e) => self.console.println(&e.to_string()),
+        }
+    }
 
+    /// Load the game from the slot and describe where the player is.
+    fn load_game(&mut self, slot: &str) {
+        if slot == "" {
+            self.console.println("Load from where? Type: load SLOT, for example: load river.");
             return;
         }
 
-        let command_one = unwrap_str(words.next());
-        let command_two = unwrap_str(words.next());
+        match read_save(&self.save_directory, slot) {
+            Ok(save) => {
+                save.restore(self);
 
-        match command {
-            "help" => self.show_help(),
-            "go" | "walk" | "move" => self.leave_room(command_one),
-            "look" => self.process_action(Action::Look),
-            "explore" => self.process_action(Action::Explore),
-            "talk" | "converse" => self.process_action(Action::Talk),
-            "q" | "quit" | "exit" => self.quit_game(),
-            _ => {
-                println!("What is: {}?", command);
-                return;
+                self.console.println(&format!("Game loaded from: {}.", slot));
+
+                self.enter_new_room();
             },
+            Err(e) => self.console.println(&e.to_string()),
         }
     }
 
-    fn show_help(&mut self) {
-        println!("\n-------------------");
-        println!("- Movement: Characters can move by typing: go/walk DIRECTION where DIRECTION equals n(orth), s(outh), e(ast) or w(est) or back.");
-        println!("- Actions: you can type an action as such: ACTION where action is look, explore or talk.");
-        println!("- Quitting: You can quit by typing: q, quit or exit.");
-        println!("-------------------");
-    }
-
     fn quit_game(&mut self) {
-        println!("\nReally? Ok. Bye.");
+        self.console.println("\nReally? Ok. Bye.");
 
         self.active = false;
     }
@@ -151,32 +363,58 @@ impl Game {
     fn process_action(&mut self, action: Action) {
 
         match action {
-            Action::Look => self.do_action(Action::Look, self.game_character.stats.clone().unwrap().int),
-            Action::Explore => self.do_action(Action::Explore, self.game_character.stats.clone().unwrap().int),
-            Action::Talk => self.do_action(Action::Talk, self.game_character.stats.clone().unwrap().chr),
+            Action::Look | Action::Explore if !self.can_see() => {
+                self.console.println("It's too dark to see anything here.");
+            },
+            Action::Look => {
+                self.show_description();
+
+                if self.current_room().has_action(&Action::Look) {
+                    self.do_action(Action::Look, Stat::Int);
+                }
+            },
+            Action::Explore => self.do_action(Action::Explore, Stat::Int),
+            Action::Talk => self.do_action(Action::Talk, Stat::Chr),
             _ => {
-                println!("You have no idea how to do that action.");
+                self.console.println("You have no idea how to do that action.");
                 return;
             }
         }
     }
 
-    fn do_action(&mut self, action: Action, stat_value: i32) {
-        let room = self.current_room.clone().unwrap();
+    fn do_action(&mut self, action: Action, stat: Stat) {
+        let room = self.current_room().clone();
 
         if action == Action::Talk && room.npcs.is_some() {
             self.talk(room.npcs);
         } else {
-            let bonus = self.stat_bonuses[stat_value as usize];
+            let bonus = self.roll_bonus(stat);
 
-            room.do_action(action, bonus);
+            room.do_action(action, bonus, self);
         }
     }
 
+    /// The bonus added to a roll with the stat, from the stat itself and the characters race.
+    ///
+    /// A character with no stats, or a stat off the end of the table, gets no bonus from the stat.
+    fn roll_bonus(&self, stat: Stat) -> i32 {
+        let stat_bonus = self.game_character.stats.as_ref()
+            .and_then(|stats| usize::try_from(stats.get(stat)).ok())
+            .and_then(|stat_value| self.stat_bonuses.get(stat_value).copied())
+            .unwrap_or(0);
+
+        return stat_bonus + self.game_character.skill_bonus(stat);
+    }
+
+    /// Can the player see in the current room? Dark rooms need darkvision.
+    fn can_see(&self) -> bool {
+        return !self.current_room().dark || self.game_character.has_trait("darkvision");
+    }
+
     fn talk(&mut self, people: Option<Vec<Person>>) {
 
         if !people.is_some() {
-            println!("Sure, talk to your self. That's not crazy at all. I do it. All the time.");
+            self.console.println("Sure, talk to your self. That's not crazy at all. I do it. All the time.");
             return;
         }
 
@@ -188,13 +426,13 @@ impl Game {
         // Else lets create the choice of people to speak to and then process that persons
         // conversation based on choice.
         if people_count == 1 {
-            people.clone().unwrap()[0].conversation.process_conversation();
+            self.converse(people.clone().unwrap()[0].clone());
         } else {
             let people = people.clone().unwrap();
 
             let mut options = menu_choices(people.clone());
 
-            display_menu(&mut options);
+            display_menu(&mut options, self.console.as_mut());
 
             let mut people_choices = HashMap::new();
             let mut count = 1;
@@ -204,89 +442,844 @@ impl Game {
                 count = count + 1;
             }
 
-            let found_person: Option<Person> = process(people_choices);
+            let found_person: Option<Person> = process(people_choices, self.console.as_mut());
 
             if found_person.is_some() {
-                found_person.unwrap().conversation.process_conversation();
+                self.converse(found_person.unwrap());
             }
         }
     }
 
-    fn leave_room(&mut self, command: &str) {
-
-        let direction_to_go: Direction;
+    /// Talk to the person the player named, for example: talk to the poet.
+    fn talk_to(&mut self, name: &str) {
+        match self.resolve_fitting(name, Target::is_person) {
+            Target::Person(person) => self.converse(person),
+            _ => self.console.println(&format!("There is no {} here to talk to.", name)),
+        }
+    }
 
-        match command {
-            "n" | "north" => direction_to_go = Direction::N,
-            "s" | "south" => direction_to_go = Direction::S,
-            "e" | "east" => direction_to_go = Direction::E,
-            "w" | "west" => direction_to_go = Direction::W,
-            "back" => direction_to_go = Direction::BACK,
-            _ => {
-                println!("You cannot go that way. Please try again.");
-                return;
-            }
+    /// Have the conversation with the person. Getting to the end of it gives xp, but only the
+    /// first time.
+    fn converse(&mut self, person: Person) {
+        if !person.conversation.process_conversation(self) {
+            return;
         }
 
-        // if we dont have a current room in this game,
-        // then we have broken some where and this should
-        // be allowed to panic.
-        let room = self.current_room.clone().unwrap();
+        let key = format!("conversation: room {}, person {}", self.current_room, person.name).to_lowercase();
+
+        self.award(key, self.world.experience.conversation, &format!("talking to {}", person.name));
+    }
 
-        if direction_to_go == Direction::BACK {
+    /// Go through the exit the player named, by its direction or its name, for example: go up or
+    /// enter house. Going back takes the player to the room they came from.
+    pub fn leave_room(&mut self, command: &str) {
+        let room = self.current_room().clone();
+
+        if Direction::from_word(command) == Some(Direction::BACK) {
 
 
             if !room.go_back.can_go_back {
                 if room.go_back.reason.is_some() {
-                    println!("{}", room.go_back.reason.clone().unwrap());
+                    self.console.println(&room.go_back.reason.clone().unwrap());
                     return;
                 } else {
-                    println!("You cannot go back. What now?");
+                    self.console.println("You cannot go back. What now?");
                     return;
                 }
             }
 
-            // Lets handel going backwards
-            //
-            // TODO: We need a way to store previvious rooms
-            // As you move forward, then pop them off.
-            // previous rooms should be a vec of rooms that we pop
-            // and push rooms we have previously been in.
-            if !self.previous_room.is_some() {
-                println!("You turn around to head back, only to discover there is no way back. What now?");
-            } else {
-                let previous_room = self.current_room.clone().unwrap();
-                let current_room = self.previous_room.clone().unwrap();
+            // Lets handel going backwards by popping the last room we were in
+            // off of the history.
+            match self.history.pop() {
+                Some(previous_room) => {
+                    self.set_current_room(previous_room);
+
+                    self.enter_new_room();
+                },
+                None => {
+                    self.console.println("You turn around to head back, only to discover there is no way back. What now?");
+                }
+            }
 
-                self.set_previous_room(previous_room);
-                self.set_current_room(current_room);
+            return;
+        }
 
-                let current_room = self.current_room.clone().unwrap();
+        let found = room.find_exit(command).and_then(|exit| exit.room.clone().map(|new_room| (exit.clone(), new_room)));
 
-                self.enter_new_room(current_room);
+        let (exit, new_room) = match found {
+            Some(found) => found,
+            _ if Direction::from_word(command).is_some() => {
+                self.console.println("You can't go that way.");
+                return;
+            },
+            _ => {
+                let mut words: Vec<String> = Direction::all().iter().map(|direction| direction.name().to_string()).collect();
+
+                words.extend(room.exits.iter().filter(|exit| !exit.hidden).filter_map(|exit| exit.name.as_ref().map(|name| name.to_lowercase())));
+
+                let words: Vec<&str> = words.iter().map(|word| word.as_str()).collect();
+                let suggestions: Vec<String> = closest(command, &words).iter().map(|word| word.to_string()).collect();
+
+                if suggestions.is_empty() {
+                    self.console.println("You cannot go that way. Please try again.");
+                } else {
+                    self.console.println(&format!("You cannot go that way. Did you mean: {}?", or_list(&suggestions)));
+                }
+
+                return;
             }
+        };
+
+        if !self.can_use_exit(&exit) {
+            return;
+        }
+
+        self.history.push(room.id);
+        self.set_current_room(new_room);
+
+        self.apply_all(&exit.effects);
+
+        self.enter_new_room();
+    }
+
+    /// Can the player go through the exit?
+    ///
+    /// Hidden exits act like they are not there until their conditions pass. Other wise
+    /// the door has to be open and the conditions have to pass. When they can't go through
+    /// we tell the player why. Passing a check on the exit gives experience the first time.
+    fn can_use_exit(&mut self, exit: &Exit) -> bool {
+        if exit.hidden && !self.check_all(&exit.conditions) {
+            self.console.println("You can't go that way.");
+            return false;
+        }
+
+        if let Some(door) = &exit.door {
+            if door.locked {
+                self.console.println(&format!("The {} is locked.", door.name));
+                return false;
+            }
+
+            if door.closed {
+                self.console.println(&format!("The {} is closed.", door.name));
+                return false;
+            }
+        }
+
+        if !exit.hidden && !self.check_all(&exit.conditions) {
+            match &exit.failure {
+                Some(failure) => self.console.println(&failure),
+                None => self.console.println("Something stops you from going that way."),
+            }
+
+            return false;
+        }
+
+        if exit.conditions.iter().any(|condition| matches!(condition, Condition::StatCheck { .. })) {
+            self.passed_check(&format!("room {}, exit {}", self.current_room, exit.word()));
+        }
+
+        return true;
+    }
+
+
+    /// Roll a d20 against a DC using the bonus for the characters stat and race.
+    fn stat_check(&mut self, stat: Stat, dc: i32) -> bool {
+        let roll = Dice::new(1, 20).with_modifier(self.roll_bonus(stat)).roll(&mut self.rng);
+
+        if roll.total > dc {
+            self.console.println(&format!("Upon your roll of a: {} ({})", roll.total, roll.breakdown()));
+            return true;
+        }
+
+        self.console.println(&format!("You failed the {} check with a roll of: {} ({}).", stat.name(), roll.total, roll.breakdown()));
+
+        return false;
+    }
+
+    /// Set a story flag.
+    pub fn set_flag(&mut self, flag: &str) {
+        self.state.set(flag, Value::Bool(true));
+    }
+
+    /// Give the player xp for what the key names, unless they have been given it already.
+    fn award(&mut self, key: String, amount: i32, reason: &str) {
+        if self.awarded.insert(key) {
+            self.gain_xp(amount, reason);
+        }
+    }
+
+    /// Find the door the player is talking about.
+    ///
+    /// Tells the player when there is no such door.
+    fn find_door(&mut self, target: &str) -> Option<Door> {
+        let exit = match self.current_room_mut().find_door(target) {
+            Some(exit) => exit.clone(),
+            None => {
+                if target == "" {
+                    self.console.println("Which way?");
+                } else {
+                    self.console.println(&format!("There is no {} here.", target));
+                }
+
+                return None;
+            }
+        };
+
+        if exit.hidden && !self.check_all(&exit.conditions) {
+            self.console.println(&format!("There is no {} here.", target));
+            return None;
+        }
+
+        return exit.door;
+    }
+
+    /// The door the player is talking about, so it can be changed.
+    ///
+    /// Only call this once find_door has found the door.
+    fn door_mut(&mut self, target: &str) -> &mut Door {
+        return self.current_room_mut().find_door(target).unwrap().door.as_mut().unwrap();
+    }
+
+    fn unlock(&mut self, target: &str) {
+        let target = &self.target_name(target);
+
+        let door = match self.find_door(target) {
+            Some(door) => door,
+            None => return,
+        };
+
+        if !door.locked {
+            self.console.println(&format!("The {} isn't locked.", door.name));
+            return;
+        }
+
+        let key = match door.key {
+            Some(key) => key,
+            None => {
+                self.console.println(&format!("There is no keyhole on the {}.", door.name));
+                return;
+            }
+        };
+
+        if !self.game_character.has_item(&key) {
+            self.console.println(&format!("You don't have the key to the {}.", door.name));
+            return;
+        }
+
+        self.door_mut(target).locked = false;
+
+        self.console.println(&format!("You unlock the {}.", door.name));
+    }
+
+    fn open(&mut self, target: &str) {
+        let target = &self.target_name(target);
+
+        let door = match self.find_door(target) {
+            Some(door) => door,
+            None => return,
+        };
+
+        if door.locked {
+            self.console.println(&format!("The {} is locked.", door.name));
+            return;
+        }
+
+        if !door.closed {
+            self.console.println(&format!("The {} is already open.", door.name));
+            return;
+        }
+
+        self.door_mut(target).closed = false;
+
+        self.console.println(&format!("You open the {}.", door.name));
+    }
+
+    /// Force a door open, or force something else in the room when there is no door.
+    fn force(&mut self, target: &str) {
+        if !self.can_use_ability(&Action::Force) {
+            return;
+        }
+
+        let target = &self.target_name(target);
+
+        if self.current_room_mut().find_door(target).is_none() {
+            self.class_action(Action::Force, target);
+            return;
+        }
+
+        let door = match self.find_door(target) {
+            Some(door) => door,
+            None => return,
+        };
+
+        if !door.locked && !door.closed {
+            self.console.println(&format!("The {} is already open.", door.name));
+            return;
+        }
+
+        let dc = match door.force_dc {
+            Some(dc) => dc,
+            None => {
+                self.console.println(&format!("The {} won't budge.", door.name));
+                return;
+            }
+        };
+
+        if self.stat_check(Stat::Str, dc) {
+            let door_mut = self.door_mut(target);
+
+            door_mut.locked = false;
+            door_mut.closed = false;
+
+            self.console.println(&format!("You force the {} open.", door.name));
+            self.passed_check(&format!("room {}, door {}", self.current_room, door.name));
         } else {
-            let new_room = room.exit(direction_to_go);
+            self.console.println(&format!("The {} holds firm. You can try again.", door.name));
+        }
+    }
+
+    /// Pick the lock on a door, or something else in the room when there is no door.
+    fn pick_lock(&mut self, target: &str) {
+        if !self.can_use_ability(&Action::PickLock) {
+            return;
+        }
 
-            if new_room.is_some() {
-                self.set_previous_room(room);
-                self.set_current_room(new_room.clone().unwrap());
+        let target = &self.target_name(target);
 
-                let current_room = self.current_room.clone().unwrap();
+        if self.current_room_mut().find_door(target).is_none() {
+            self.class_action(Action::PickLock, target);
+            return;
+        }
 
-                self.enter_new_room(current_room);
-            } else {
-                println!("You can't go that way.");
+        let door = match self.find_door(target) {
+            Some(door) => door,
+            None => return,
+        };
+
+        if !door.locked {
+            self.console.println(&format!("The {} isn't locked.", door.name));
+            return;
+        }
+
+        let dc = match door.pick_dc {
+            Some(dc) => dc,
+            None => {
+                self.console.println(&format!("The lock on the {} is too well made to pick.", door.name));
+                return;
+            }
+        };
+
+        if self.stat_check(Stat::Dex, dc) {
+            self.door_mut(target).locked = false;
+
+            self.console.println(&format!("You pick the lock on the {}.", door.name));
+            self.passed_check(&format!("room {}, door {}", self.current_room, door.name));
+        } else {
+            self.console.println(&format!("The lock on the {} won't give. You can try again.", door.name));
+        }
+    }
+
+    /// Cast a spell, for example: cast light or cast charm on the poet.
+    fn cast(&mut self, words: &str) {
+        if words == "" {
+            self.console.println("Cast what? Type: cast SPELL, for example: cast light.");
+            return;
+        }
+
+        let (spell, target) = match words.split_once(" on ") {
+            Some((spell, target)) => (spell.trim(), target.trim()),
+            None => (words, ""),
+        };
+
+        self.class_action(Action::Cast(spell.to_string()), target);
+    }
+
+    /// Can the players class use the ability, and have they reached the level it unlocks at?
+    /// When they can't we tell the player.
+    fn can_use_ability(&mut self, action: &Action) -> bool {
+        let class = self.game_character.class.clone();
+
+        let (allowed, reason, ability) = match action {
+            Action::Cast(_) => (class.map_or(false, |class| class.can_cast), "You don't know how to cast spells.", "cast"),
+            Action::Steal => (class.map_or(false, |class| class.can_steal), "You don't have the light fingers of a thief.", "steal"),
+            Action::PickLock => (class.map_or(false, |class| class.can_steal), "You don't have the light fingers of a thief.", "pick lock"),
+            Action::Force => (class.map_or(false, |class| class.can_force), "You don't have the training of a fighter.", "force"),
+            Action::Intimidate => (class.map_or(false, |class| class.can_force), "You don't have the training of a fighter.", "intimidate"),
+            _ => return true,
+        };
+
+        if !allowed {
+            self.console.println(reason);
+            return false;
+        }
+
+        return self.has_unlocked(ability);
+    }
+
+    /// Use a class ability on someone in the room, or on the room itself.
+    fn class_action(&mut self, action: Action, target: &str) {
+        if !self.can_use_ability(&action) {
+            return;
+        }
+
+        let bonus = self.roll_bonus(match action {
+            Action::Cast(_) => Stat::Int,
+            Action::Steal | Action::PickLock => Stat::Dex,
+            _ => Stat::Str,
+        });
+
+        if self.respond(&action, target, bonus) {
+            return;
+        }
+
+        match action {
+            Action::Cast(spell) => self.console.println(&format!("You cast {}, but nothing happens.", spell)),
+            Action::Steal => self.console.println("There's nothing here to steal."),
+            Action::PickLock => self.console.println("There's no lock here to pick."),
+            Action::Force => self.console.println("There's nothing here to force."),
+            _ => self.console.println("There's no one here to intimidate."),
+        }
+    }
+
+    /// Have someone in the room, or the room itself, respond to the action.
+    ///
+    /// When the player names someone in the room they respond. When the player doesn't name
+    /// anyone, the room responds if it can, other wise the one person in the room who responds
+    /// to the action does.
+    ///
+    /// Returns false when nothing responded and the player hasn't been told anything yet.
+    fn respond(&mut self, action: &Action, target: &str, bonus: i32) -> bool {
+        let room = self.current_room().clone();
+        let people = room.npcs.clone().unwrap_or_default();
+
+        let person = if target == "" {
+            let responding: Vec<&Person> = people.iter().filter(|person| person.action(action).is_some()).collect();
+
+            if responding.len() == 1 && !room.has_action(action) { Some(responding[0].clone()) } else { None }
+        } else {
+            match self.resolve_fitting(target, Target::is_person) {
+                Target::Person(person) => Some(person),
+                _ => None,
+            }
+        };
+
+        if let Some(person) = person {
+            match person.action(action) {
+                Some(on_action) => {
+                    on_action.perform(bonus, self);
+                },
+                None => self.console.println(&format!("{} pays you no mind.", person.name)),
+            }
+
+            return true;
+        }
+
+        if target != "" {
+            self.console.println(&format!("There is no {} here.", target));
+            return true;
+        }
+
+        if room.has_action(action) {
+            room.do_action(action.clone(), bonus, self);
+            return true;
+        }
+
+        return false;
+    }
+
+    /// Do a command the adventure added. Someone in the room, or the room itself, responds
+    /// to it, other wise the command does what the adventure says it does on its own.
+    fn adventure_command(&mut self, command: &Command) {
+        let verb = match self.world.find_verb(&command.verb) {
+            Some(verb) => verb.clone(),
+            None => {
+                self.console.println(&format!("What is: {}?", command.verb));
+                return;
+            }
+        };
+
+        let bonus = self.roll_bonus(verb.stat);
+
+        if self.respond(&Action::Custom(verb.name.clone()), &command.object, bonus) {
+            return;
+        }
+
+        match &verb.on_action {
+            Some(on_action) => {
+                on_action.perform(bonus, self);
+            },
+            None => self.console.println("Nothing happens."),
+        }
+    }
+
+    /// Pick up an item in the current room, by its name or part of it.
+    pub fn take_item(&mut self, name: &str) {
+        if !self.can_see() {
+            self.console.println("You feel around in the dark, but can't find it.");
+            return;
+        }
+
+        let name = &self.target_name_fitting(name, Target::is_room_item);
+
+        match self.current_room_mut().take_item(name) {
+            Some(item) => {
+                self.console.println(&format!("You take the {}.", item.name));
+
+                self.game_character.add_item(item);
+            },
+            None => self.console.println(&format!("There is no {} here.", name)),
+        }
+    }
+
+    /// Drop an item the player is carrying in the current room.
+    pub fn drop_item(&mut self, name: &str) {
+        let name = &self.target_name_fitting(name, Target::is_carried);
+
+        match self.game_character.remove_item(name) {
+            Some(item) => {
+                self.console.println(&format!("You drop the {}.", item.name));
+
+                self.current_room_mut().drop_item(item);
+            },
+            None => self.console.println(&format!("You are not carrying a {}.", name)),
+        }
+    }
+
+    fn show_inventory(&mut self) {
+        if self.game_character.inventory.is_empty() {
+            self.console.println("You are not carrying anything.");
+            return;
+        }
+
+        self.console.println("\n-------------------");
+        self.console.println("You are carrying:");
+
+        for item in &self.game_character.inventory {
+            self.console.println(&format!("- {} (weight: {})", item.name, item.weight));
+        }
+
+        self.console.println(&format!("Total weight: {}", self.game_character.carried_weight()));
+        self.console.println("-------------------");
+    }
+
+    /// Look at something in the room or in the inventory: an item, a person, an enemy or an exit.
+    ///
+    /// Looking at a container shows what is in it.
+    fn examine(&mut self, name: &str) {
+        match self.resolve(name) {
+            Target::Carried(item) | Target::Item(item) => {
+                self.console.println(&format!("{}: {}", item.name, item.description));
+
+                if let Some(contents) = &item.contents {
+                    let names: Vec<&str> = contents.iter().map(|content| content.name.as_str()).collect();
+
+                    if names.is_empty() {
+                        self.console.println(&format!("The {} is empty.", item.name));
+                    } else {
+                        self.console.println(&format!("In the {}: {}", item.name, names.join(", ")));
+                    }
+                }
+            },
+            Target::Person(person) => self.console.println(&format!("You see {}. You could talk to them.", person.name)),
+            Target::Enemy(enemy) => self.console.println(&format!("The {} watches you. HP: {}/{}", enemy.name, enemy.hp, enemy.max_hp)),
+            Target::Exit(exit) => match &exit.door {
+                Some(door) if door.locked => self.console.println(&format!("The {} to the {} is locked.", door.name, exit.direction.name())),
+                Some(door) if door.closed => self.console.println(&format!("The {} to the {} is closed.", door.name, exit.direction.name())),
+                Some(door) => self.console.println(&format!("The {} to the {} is open.", door.name, exit.direction.name())),
+                None if exit.direction == Direction::NONE => self.console.println(&format!("A way leads through the {}.", exit.word())),
+                None => self.console.println(&format!("A way leads {}.", exit.label())),
+            },
+            Target::Nothing => self.console.println(&format!("You don't see a {} here.", name)),
+        }
+    }
+
+    /// Take an item out of a container in the room or in the inventory, for example:
+    /// take key from chest.
+    fn take_from(&mut self, name: &str, container: &str) {
+        let (carried, container) = match self.resolve_fitting(container, Target::is_item) {
+            Target::Carried(item) if item.is_container() => (true, item.name),
+            Target::Item(item) if item.is_container() => (false, item.name),
+            Target::Carried(item) | Target::Item(item) => {
+                self.console.println(&format!("You can't take anything from the {}.", item.name));
+                return;
+            },
+            _ => {
+                self.console.println(&format!("There is no {} here.", container));
+                return;
+            },
+        };
+
+        let holder = if carried {
+            self.game_character.inventory.iter_mut().find(|item| item.name == container)
+        } else {
+            self.current_room_mut().items.iter_mut().find(|item| item.name == container)
+        }.unwrap();
+
+        let holder_name = holder.name.clone();
+
+        match holder.take_content(name) {
+            Some(item) => {
+                self.console.println(&format!("You take the {} from the {}.", item.name, holder_name));
+
+                self.game_character.add_item(item);
+            },
+            None => self.console.println(&format!("There is no {} in the {}.", name, holder_name)),
+        }
+    }
+
+    /// Put an item the player is carrying into a container, for example: put letter in chest.
+    fn put_item(&mut self, name: &str, container: &str) {
+        if container == "" {
+            self.console.println(&format!("Put the {} where?", name));
+            return;
+        }
+
+        let item = match self.resolve_fitting(name, Target::is_carried) {
+            Target::Carried(item) => item,
+            _ => {
+                self.console.println(&format!("You are not carrying a {}.", name));
+                return;
             }
+        };
+
+        let (carried, container) = match self.resolve_fitting(container, Target::is_item) {
+            Target::Carried(holder) if holder.is_container() && holder != item => (true, holder.name),
+            Target::Item(holder) if holder.is_container() => (false, holder.name),
+            Target::Carried(holder) | Target::Item(holder) => {
+                self.console.println(&format!("You can't put anything in the {}.", holder.name));
+                return;
+            },
+            _ => {
+                self.console.println(&format!("There is no {} here.", container));
+                return;
+            },
+        };
+
+        let item = self.game_character.remove_item(&item.name).unwrap();
+
+        let holder = if carried {
+            self.game_character.inventory.iter_mut().find(|holder| holder.name == container)
+        } else {
+            self.current_room_mut().items.iter_mut().find(|holder| holder.name == container)
+        }.unwrap();
+
+        let message = format!("You put the {} in the {}.", item.name, holder.name);
+
+        holder.contents.as_mut().unwrap().push(item);
+
+        self.console.println(&message);
+    }
+
+    /// Give an item the player is carrying to a person in the room, for example: give letter to poet.
+    ///
+    /// The person responds with their give action for the item. When it goes ahead the item is
+    /// handed over, other wise the player keeps it.
+    fn give(&mut self, name: &str, to: &str) {
+        let item = match self.resolve_fitting(name, Target::is_carried) {
+            Target::Carried(item) => item,
+            _ => {
+                self.console.println(&format!("You are not carrying a {}.", name));
+                return;
+            }
+        };
+
+        if to == "" {
+            self.console.println(&format!("Give the {} to who?", item.name));
+            return;
+        }
+
+        let person = match self.resolve_fitting(to, Target::is_person) {
+            Target::Person(person) => person,
+            _ if self.is_named(to) => {
+                self.console.println(&format!("You can't give anything to the {}.", to));
+                return;
+            },
+            _ => {
+                self.console.println(&format!("There is no {} here.", to));
+                return;
+            },
+        };
+
+        let mut names = vec![item.name.clone()];
+
+        names.extend(item.aliases.clone());
+
+        let on_action = names.iter().find_map(|name| person.action(&Action::Give(name.to_lowercase())));
+
+        let on_action = match on_action {
+            Some(on_action) => on_action.clone(),
+            None => {
+                self.console.println(&format!("{} doesn't want the {}.", person.name, item.name));
+                return;
+            }
+        };
+
+        let bonus = self.roll_bonus(Stat::Chr);
+
+        if on_action.perform(bonus, self) {
+            if let Some(item) = self.game_character.remove_item(&item.name) {
+                self.console.println(&format!("You hand over the {}.", item.name));
+            }
+        }
+    }
+
+    /// Fight the enemy the player names, or every enemy in the room when they don't.
+    fn start_fight(&mut self, target: &str) {
+        let room = self.current_room().clone();
+
+        if room.enemies.is_empty() {
+            self.console.println("There's nothing here to fight.");
+            return;
+        }
+
+        if target == "" {
+            self.pending_fight = room.enemies.iter().map(|enemy| enemy.name.clone()).collect();
+            return;
+        }
+
+        match self.resolve_fitting(target, Target::is_enemy) {
+            Target::Enemy(enemy) => self.pending_fight.push(enemy.name),
+            _ => self.console.println(&format!("There is no {} here to fight.", target)),
         }
     }
 
-    fn enter_new_room(&mut self, current_room: Room) {
-        println!("\n");
-        println!("Location: {}", current_room.name());
-        println!("\n");
-        println!("{}", current_room.describe());
-        println!("\nWhat do you do? (type help for commands)");
+    fn show_health(&mut self) {
+        self.console.println(&format!("HP: {}/{}", self.game_character.hp, self.game_character.max_hp()));
+    }
+
+    fn show_history(&mut self) {
+        let mut path: Vec<String> = Vec::new();
+
+        for room in &self.history {
+            path.push(self.world.room(room).name().clone());
+        }
+
+        path.push(format!("{} (you are here)", self.current_room().name()));
+
+        self.console.println("\n-------------------");
+        self.console.println(&format!("The path you took: {}", path.join(" -> ")));
+        self.console.println("-------------------");
+    }
+
+    /// Show the description of the current room, with what the player can see in it and the
+    /// way out.
+    pub fn show_description(&mut self) {
+        let room = self.current_room().clone();
+        let can_see = self.can_see();
+
+        let paragraphs = room.description(can_see, self).render();
+
+        self.console.println(&paragraphs.join("\n\n"));
+    }
+
+    fn enter_new_room(&mut self) {
+        let current_room = self.current_room().clone();
+
+        self.console.println("\n");
+        self.console.println(&format!("Location: {}", current_room.name()));
+        self.console.println("\n");
+
+        self.show_description();
+
+        for enemy in current_room.enemies.iter().filter(|enemy| enemy.hostile) {
+            self.pending_fight.push(enemy.name.clone());
+        }
+
+        self.console.println("\nWhat do you do? (type help for commands)");
+    }
+}
+
+/// The game checks conditions and applies effects for the world.
+impl Context for Game {
+
+    fn console(&mut self) -> &mut dyn Console {
+        return self.console.as_mut();
+    }
+
+    fn rng(&mut self) -> &mut Rng {
+        return &mut self.rng;
+    }
+
+    fn check(&mut self, condition: &Condition) -> bool {
+        match condition {
+            Condition::HasItem(item) => self.game_character.has_item(item),
+            Condition::Flag(key) => self.state.flag(key),
+            Condition::NotFlag(key) => !self.state.flag(key),
+            Condition::Equals { key, value } => self.state.get(key) == Some(value),
+            Condition::AtLeast { key, value } => self.state.int(key) >= *value,
+            Condition::StatCheck { stat, dc } => self.stat_check(*stat, *dc),
+            Condition::MinStat { stat, value } => self.game_character.stats.clone().unwrap().get(*stat) >= *value,
+            Condition::Class(name) => match &self.game_character.class {
+                Some(class) => class.name.to_lowercase() == name.to_lowercase(),
+                None => false,
+            },
+            Condition::CanCast => self.game_character.class.as_ref().map_or(false, |class| class.can_cast),
+            Condition::CanSteal => self.game_character.class.as_ref().map_or(false, |class| class.can_steal),
+            Condition::CanForce => self.game_character.class.as_ref().map_or(false, |class| class.can_force),
+            Condition::Trait(name) => self.game_character.has_trait(name),
+            Condition::Disposition { npc, value } => {
+                let current_room = self.current_room.clone();
+
+                match self.world.find_person_mut(&current_room, npc) {
+                    Some(person) => person.disposition >= *value,
+                    None => false,
+                }
+            },
+        }
+    }
+
+    fn apply(&mut self, effect: &Effect) {
+        match effect {
+            Effect::Set { key, value } => self.state.set(key, value.clone()),
+            Effect::Add { key, amount } => self.state.add(key, *amount),
+            Effect::GiveItem(item) => {
+                self.console.println(&format!("You receive: {}", item.name));
+
+                self.game_character.add_item(item.clone());
+            },
+            Effect::TakeItem(name) => {
+                if let Some(item) = self.game_character.remove_item(name) {
+                    self.console.println(&format!("You hand over the {}.", item.name));
+                }
+            },
+            Effect::Disposition { npc, amount } => {
+                let current_room = self.current_room.clone();
+
+                if let Some(person) = self.world.find_person_mut(&current_room, npc) {
+                    person.disposition = person.disposition + amount;
+                }
+            },
+            Effect::Damage(dice) => {
+                let roll = dice.roll(&mut self.rng);
+
+                self.game_character.damage(roll.total);
+
+                self.console.println(&format!("You take {} damage ({}). HP: {}/{}", roll.total.max(0), roll.breakdown(), self.game_character.hp, self.game_character.max_hp()));
+            },
+            Effect::Heal(dice) => {
+                let roll = dice.roll(&mut self.rng);
+
+                self.game_character.heal(roll.total);
+
+                self.console.println(&format!("You heal {} hit points ({}). HP: {}/{}", roll.total.max(0), roll.breakdown(), self.game_character.hp, self.game_character.max_hp()));
+            },
+            Effect::Fight(name) => {
+                if let Some(enemy) = self.current_room().find_enemy(name) {
+                    self.pending_fight.push(enemy.name.clone());
+                }
+            },
+            Effect::Xp { amount, key } => {
+                self.award(format!("effect: {}", key), *amount, "your deeds");
+            },
+        }
+    }
+
+    fn passed_check(&mut self, key: &str) {
+        self.award(format!("check: {}", key).to_lowercase(), self.world.experience.check, "passing the check");
+    }
+
+    fn is_defeated(&mut self) -> bool {
+        return self.game_character.is_defeated();
     }
 }
 
@@ -296,32 +1289,26 @@ impl Game {
 ///
 /// Will run a loop until the player either enters there name.
 ///
-/// The given options for the input are: name or quit.
-pub fn create_character() -> Character {
-    println!("Whats your name? (you can type quit, q or exit to exit)");
+/// The given options for the input are: name or quit. Returns None when the player quits, or the
+/// input runs out.
+pub fn create_character(console: &mut dyn Console) -> Option<Character> {
+    console.println("Whats your name? (you can type quit, q or exit to exit)");
 
     let mut done = false;
 
     let mut name: String = String::new();
 
     while !done {
-        print!("> ");
-
-        io::stdout().flush().expect("Error flushing stdout!");
-
-        let mut input = String::new();
-
-        io::stdin().read_line(&mut input)
-                   .expect("Error reading stdin!");
-
-        if input.ends_with('\n') {
-           input.pop();
-        }
+        // Running out of input is the same as typing quit.
+        let input = match console.prompt() {
+            Some(input) => input,
+            None => "quit".to_string(),
+        };
 
         let words: Vec<&str> = input.split_whitespace().collect();
 
         if words.is_empty() {
-            println!("Invalid input.");
+            console.println("Invalid input.");
         } else {
             let mut result: Vec<String> = Vec::new();
 
@@ -329,25 +1316,28 @@ pub fn create_character() -> Character {
                 result.push(word.to_string());
             }
 
-            if parse_input(result) {
-                name = input;
-                done = true;
+            if !parse_input(result, console) {
+                return None;
             }
+
+            name = input;
+            done = true;
         }
     }
 
-    return build_character(name);
+    return Some(build_character(name));
 }
 
-fn parse_input(words: Vec<String>) -> bool {
+/// Is the name one the player can have? Quitting is not a name.
+fn parse_input(words: Vec<String>, console: &mut dyn Console) -> bool {
     let mut words = words.iter();
 
     let command = unwrap_str(words.next());
 
     match command {
         "quit" | "q" | "exit" => {
-            println!("Really? Ok, bye!");
-            process::exit(1);
+            console.println("Really? Ok, bye!");
+            return false;
         },
         _ => {
             return true;
@@ -1,7 +1,6 @@
-use std::io;
-use std::io::Write;
 use std::vec::Vec;
 use std::collections::HashMap;
+use core::console::Console;
 use core::text_handeling::unwrap_str;
 use world::person::{Person};
 
@@ -19,30 +18,22 @@ pub fn menu_choices(people: Vec<Person>) -> HashMap<i32, String> {
     return options;
 }
 
-pub fn process(options: HashMap<i32, Person>) -> Option<Person> {
+pub fn process(options: HashMap<i32, Person>, console: &mut dyn Console) -> Option<Person> {
 
     let mut done: bool = false;
 
     let mut found_person: Option<Person> = None;
 
     while !done {
-        print!("> ");
-
-        io::stdout().flush().expect("Error flushing stdout!");
-
-        let mut input = String::new();
-
-        io::stdin().read_line(&mut input)
-                   .expect("Error reading stdin!");
-
-        if input.ends_with('\n') {
-           input.pop();
-        }
+        let input = match console.prompt() {
+            Some(input) => input,
+            None => return None,
+        };
 
         let words: Vec<&str> = input.split_whitespace().collect();
 
         if words.is_empty() {
-            println!("Invalid input.");
+            console.println("Invalid input.");
         } else {
             let mut result: Vec<String> = Vec::new();
 
@@ -50,11 +41,11 @@ pub fn process(options: HashMap<i32, Person>) -> Option<Person> {
                 result.push(word.to_string());
             }
 
-            if parse_quit(result.clone()) {
+            if parse_quit(result.clone(), console) {
                 done = true;
             }
 
-            let person = parse_choice_input(result.clone(), options.clone());
+            let person = parse_choice_input(result.clone(), options.clone(), console);
 
             if person.is_some() {
                 found_person = person;
@@ -66,7 +57,7 @@ pub fn process(options: HashMap<i32, Person>) -> Option<Person> {
     return found_person;
 }
 
-fn parse_choice_input(words: Vec<String>, options: HashMap<i32, Person>) -> Option<Person> {
+fn parse_choice_input(words: Vec<String>, options: HashMap<i32, Person>, console: &mut dyn Console) -> Option<Person> {
     let mut words = words.iter();
 
     let input = unwrap_str(words.next());
@@ -76,7 +67,7 @@ fn parse_choice_input(words: Vec<String>, options: HashMap<i32, Person>) -> Opti
             if options.contains_key(&n) {
                 return Some(options[&n].clone());
             } else {
-                println!("Not a valid choice.");
+                console.println("Not a valid choice.");
 
                 return None;
             }
@@ -85,11 +76,11 @@ fn parse_choice_input(words: Vec<String>, options: HashMap<i32, Person>) -> Opti
         Err(_e) => {
             match input {
                 "quit" | "q" | "exit" => {
-                    println!("Conversation over.");
+                    console.println("Conversation over.");
                     return None;
                 },
                 _ => {
-                    println!("invalid input");
+                    console.println("invalid input");
                     return None;
                 }
             }
@@ -97,14 +88,14 @@ fn parse_choice_input(words: Vec<String>, options: HashMap<i32, Person>) -> Opti
     }
 }
 
-fn parse_quit(words: Vec<String>) -> bool {
+fn parse_quit(words: Vec<String>, console: &mut dyn Console) -> bool {
     let mut words = words.iter();
 
     let command = unwrap_str(words.next());
 
     match command {
         "quit" | "q" | "exit" => {
-            println!("You turn away from the people. You can talk again or do other actions in the room. Type help for more information.");
+            console.println("You turn away from the people. You can talk again or do other actions in the room. Type help for more information.");
             return true;
         },
         _ => {
@@ -1,18 +1,23 @@
 use std::collections::HashMap;
+use core::console::Console;
 use core::text_handeling::unwrap_str;
 
 /// Display the menu for the player to make choices from.
-pub fn display_menu(choices: &mut HashMap<i32, String>) {
+pub fn display_menu(choices: &mut HashMap<i32, String>, console: &mut dyn Console) {
 
-    println!("\n===== [Choices] =====");
+    console.println("\n===== [Choices] =====");
 
-    for (key, value) in &*choices {
-        print!("{}) {}", key, value);
+    let mut keys: Vec<&i32> = choices.keys().collect();
+
+    keys.sort();
+
+    for key in keys {
+        console.println(&format!("{}) {}", key, choices[key]));
     }
 
-    println!("\n=====================");
-    println!("Choose one by typing the number or q, quit or exit to leave the conversation.");
-    println!("\n");
+    console.println("=====================");
+    console.println("Choose one by typing the number or q, quit or exit to leave the conversation.");
+    console.println("\n");
 }
 
 /// Parsing the quit options
@@ -22,14 +27,14 @@ pub fn display_menu(choices: &mut HashMap<i32, String>) {
 ///
 /// Only call this method with in the context of the choice
 /// selection loop. the idea is to kill the loop.
-pub fn parse_quit(words: Vec<String>) -> bool {
+pub fn parse_quit(words: Vec<String>, console: &mut dyn Console) -> bool {
     let mut words = words.iter();
 
     let command = unwrap_str(words.next());
 
     match command {
         "quit" | "q" | "exit" => {
-            println!("You abruptly left the conversation.");
+            console.println("You abruptly left the conversation.");
             return true;
         },
         _ => {
@@ -1,12 +1,14 @@
-use std::io;
-use std::io::Write;
 use std::collections::HashMap;
 use std::vec::Vec;
 use std::boxed::Box;
+use std::env;
+use std::panic;
 use std::process;
+use core::console::{Console, Terminal};
 use core::launch_adventure::start_adventure;
 use core::create_adventures::make_adventure_list;
 use core::process_call_backs::SimpleCallback;
+use core::rng::{self, Rng};
 use adventures::dark_harvest::run_dark_harvest;
 use character::charactersheet::Character;
 use game::create_character;
@@ -15,12 +17,12 @@ use game::create_character;
 ///
 /// Each of the adeventures are stored in a hash map that stores the number (input)
 /// to match against and the callback that launches the adventure when the inout matches.
-fn get_adventures(character: Character) -> HashMap<i32, SimpleCallback> {
+fn get_adventures(character: Character, rng: Rng) -> HashMap<i32, SimpleCallback> {
 
     let mut adventures = Vec::new();
 
     let dark_harvest = SimpleCallback {
-        callback: Box::new(|| run_dark_harvest(character))
+        callback: Box::new(|| run_dark_harvest(character, Box::new(Terminal::new()), rng))
     };
 
     adventures.push(dark_harvest);
@@ -28,42 +30,92 @@ fn get_adventures(character: Character) -> HashMap<i32, SimpleCallback> {
     return make_adventure_list(adventures);
 }
 
-fn main() {
+/// Get the seed from the command line, for example: `story_teller --seed 42`.
+///
+/// Running with the same seed and typing the same things plays out the same way. With out
+/// a seed we pick one from the clock.
+fn parse_seed(args: Vec<String>) -> Rng {
+    let seed = match args.get(1).map(|arg| arg.as_str()) {
+        None => return Rng::from_time(),
+        Some("--seed") => args.get(2).cloned(),
+        Some(arg) => match arg.strip_prefix("--seed=") {
+            Some(seed) => Some(seed.to_string()),
+            None => {
+                eprintln!("Unknown option: {}. The only option is: --seed NUMBER", arg);
+                process::exit(1);
+            }
+        },
+    };
 
-    let character = create_character();
+    match seed.and_then(|seed| seed.parse::<u64>().ok()) {
+        Some(seed) => Rng::new(seed),
+        None => {
+            eprintln!("--seed needs a number, for example: --seed 42");
+            process::exit(1);
+        }
+    }
+}
 
-    println!("\nWelcome {}, the available adventures for you are:", character.name);
-    println!("\n========[ Adventures ]========");
-    println!("1) Dark Harvest");
-    println!("==============================");
-    println!("Please Choose by typing the number beside the name: (You can also type quit to exit) ");
+/// Print the seed when the game crashes so it can go in the bug report.
+///
+/// Loading a save carries on with the saves seed, so we report the seed and state of the
+/// generator the game was last rolling with, falling back to the seed from the command line.
+fn report_seed_on_panic(seed: u64) {
+    let default_hook = panic::take_hook();
+
+    panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+
+        match rng::reported() {
+            Some((reported_seed, state)) => {
+                eprintln!("\nThe game crashed. Please include this in your bug report: seed {}, rng state {}", reported_seed, state);
+                eprintln!("You can play the same game again with: story_teller --seed {}", reported_seed);
+            },
+            None => {
+                eprintln!("\nThe game crashed. Please include this in your bug report: seed {}", seed);
+                eprintln!("You can play the same game again with: story_teller --seed {}", seed);
+            },
+        }
+    }));
+}
 
-    println!("\nOnce you select an adventure you can then setup your character by selecting a race, class and set your stats.");
+fn main() {
 
-    let mut done = false;
+    let rng = parse_seed(env::args().collect());
 
-    let mut choice = String::new();
+    report_seed_on_panic(rng.seed());
 
-    while !done {
-        print!("> ");
+    let mut console = Terminal::new();
 
-        io::stdout().flush().expect("Error flushing stdout!");
+    let character = match create_character(&mut console) {
+        Some(character) => character,
+        None => process::exit(1),
+    };
 
-        let mut input = String::new();
+    console.println(&format!("\nWelcome {}, the available adventures for you are:", character.name));
+    console.println("\n========[ Adventures ]========");
+    console.println("1) Dark Harvest");
+    console.println("==============================");
+    console.println("Please Choose by typing the number beside the name: (You can also type quit to exit) ");
 
-        io::stdin().read_line(&mut input)
-                   .expect("Error reading stdin!");
+    console.println("\nOnce you select an adventure you can then setup your character by selecting a race, class and set your stats.");
 
-        if input.ends_with('\n') {
-           input.pop();
-        }
+    let mut done = false;
+
+    let mut choice = String::new();
+
+    while !done {
+        let input = match console.prompt() {
+            Some(input) => input,
+            None => "quit".to_string(),
+        };
 
         let words: Vec<&str> = input.split_whitespace().collect();
 
         if words.is_empty() {
-            println!("Invalid input. Try again.");
-        } else if input.to_string() == "quit".to_string() {
-            println!("Bye now!");
+            console.println("Invalid input. Try again.");
+        } else if input == "quit" {
+            console.println("Bye now!");
             process::exit(1);
         } else {
             choice = input;
@@ -71,5 +123,5 @@ fn main() {
         }
     }
 
-    start_adventure(choice, get_adventures(character));
+    start_adventure(choice, get_adventures(character, rng));
 }
@@ -0,0 +1,128 @@
+use core::console::MemoryConsole;
+use character::charactersheet::{build_character, find_class, Character, Stats};
+use game::Game;
+use game::commands::{Arguments, GameCommand, Registry};
+use world::loader::{load_world_from_str, Format};
+
+const WORLD: &str = r#"
+start = "hall"
+
+[[rooms]]
+id = "hall"
+name = "Hall"
+description = "A long hall."
+"#;
+
+fn wizard() -> Character {
+    let mut character = build_character("Child".to_string());
+
+    character.class = find_class("wizard");
+    character.stats = Some(Stats::new(10, 10, 10, 10, 10));
+    character.hp = character.max_hp();
+
+    character
+}
+
+fn command(name: &str, aliases: Vec<&str>) -> GameCommand {
+    GameCommand::new(name.to_string(), name.to_string(), "does a thing.".to_string(), Arguments::Nothing, |game, _| game.console.println("You dance a jig."))
+        .with_aliases(aliases.iter().map(|alias| alias.to_string()).collect())
+}
+
+#[test]
+fn registering_a_verb_takes_it_over() {
+    let mut registry = Registry::standard();
+
+    registry.register(command("search", vec!["x"]));
+
+    assert_eq!(registry.find("x").unwrap().name, "search");
+    assert_eq!(registry.find("examine").unwrap().aliases, vec!["inspect"]);
+
+    registry.register(command("look", Vec::new()));
+
+    assert!(registry.find("l").is_none());
+    assert_eq!(registry.commands.iter().filter(|command| command.name == "look").count(), 1);
+}
+
+#[test]
+fn help_is_made_from_the_registry() {
+    let help = Registry::standard().help();
+
+    assert!(help.contains(&"- take ITEM (from CONTAINER): pick up an item, or take it out of a container. (also: get, pick up)".to_string()));
+    assert!(help.contains(&"- quit: leave the game. (also: q, exit)".to_string()));
+}
+
+#[test]
+fn adventures_can_add_commands() {
+    let world = load_world_from_str(WORLD, Format::Toml, "commands.toml").unwrap();
+
+    let console = MemoryConsole::new(vec!["dance", "help", "dance wildly", "inventory key", "take", "quit"]);
+    let output = console.output();
+
+    let mut game = Game::new(wizard(), world, Box::new(console)).with_command(command("dance", Vec::new()));
+
+    game.run();
+
+    assert!(output.contains("You dance a jig."));
+    assert!(output.contains("- dance: does a thing."));
+    assert!(output.contains("Just type: dance"));
+    assert!(output.contains("Just type: inventory"));
+    assert!(output.contains("Take what?"));
+}
+
+#[test]
+fn a_command_can_start_with_any_letter() {
+    let world = load_world_from_str(WORLD, Format::Toml, "commands.toml").unwrap();
+
+    let console = MemoryConsole::new(vec!["élan", "quit"]);
+    let output = console.output();
+
+    let command = GameCommand::new("élan".to_string(), "élan THING".to_string(), "does a thing with flair.".to_string(), Arguments::Required, |game, _| game.console.println("With flair!"));
+
+    let mut game = Game::new(wizard(), world, Box::new(console)).with_command(command);
+
+    game.run();
+
+    assert!(output.contains("Élan what?"));
+}
+
+#[test]
+fn the_default_registry_has_the_standard_commands() {
+    assert_eq!(Registry::default().help(), Registry::standard().help());
+}
+
+#[test]
+fn a_handler_can_move_and_take_things() {
+    let world = load_world_from_str(r#"
+start = "hall"
+
+[[rooms]]
+id = "hall"
+name = "Hall"
+description = "A long hall."
+exits = [{ direction = "north", room = "vault" }]
+
+[[rooms]]
+id = "vault"
+name = "Vault"
+description = "A cold vault."
+items = [{ name = "Crown", description = "A golden crown." }]
+"#, Format::Toml, "commands.toml").unwrap();
+
+    let console = MemoryConsole::new(vec!["sneak north", "grab crown", "inventory", "quit"]);
+    let output = console.output();
+
+    let sneak = GameCommand::new("sneak".to_string(), "sneak DIRECTION".to_string(), "move quietly.".to_string(), Arguments::Required, |game, command| {
+        game.console.println("You creep along.");
+        game.leave_room(&command.object);
+    });
+
+    let grab = GameCommand::new("grab".to_string(), "grab ITEM".to_string(), "snatch an item.".to_string(), Arguments::Required, |game, command| game.take_item(&command.object));
+
+    let mut game = Game::new(wizard(), world, Box::new(console)).with_command(sneak).with_command(grab);
+
+    game.run();
+
+    assert!(output.contains("You creep along."));
+    assert!(output.contains("A cold vault."));
+    assert!(output.contains("You take the Crown."));
+}
@@ -0,0 +1,36 @@
+use character::charactersheet::build_character;
+use core::console::MemoryConsole;
+use core::rng::{self, Rng};
+use game::Game;
+use game::save::Save;
+use world::loader::{load_world_from_str, Format};
+
+const WORLD: &str = r#"
+start = "road"
+
+[[rooms]]
+id = "road"
+name = "Road"
+description = "A quiet road."
+"#;
+
+// The reported generator is kept for the whole process, so this is the only test in this file.
+#[test]
+fn a_crash_reports_the_generator_of_a_loaded_save() {
+    let world = load_world_from_str(WORLD, Format::Toml, "road.toml").unwrap();
+
+    let mut saved = Game::new(build_character("Child".to_string()), world.clone(), Box::new(MemoryConsole::new(vec![]))).with_seed(99);
+    saved.rng.gen_range(1, 20);
+
+    let save = Save::from_game(&saved);
+
+    let mut game = Game::new(build_character("Child".to_string()), world, Box::new(MemoryConsole::new(vec!["look"]))).with_rng(Rng::new(1));
+    save.restore(&mut game);
+
+    assert_eq!(rng::reported(), Some((99, saved.rng.state())));
+
+    game.rng.gen_range(1, 20);
+    game.run();
+
+    assert_eq!(rng::reported(), Some((99, game.rng.state())));
+}
@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+use adventures::dark_harvest::dark_harvest_world;
+use world::World;
+use world::actions::{Action, OnAction};
+use world::conversation::{Choices, Conversation, Converse};
+use world::person::Person;
+use world::room::{make_exit, Direction, Exit, GoBack, Room};
+
+/// The Dark Harvest world as it was built in Rust before it moved to its adventure file.
+fn built_in_rust() -> World {
+    let mut clearing = HashMap::new();
+
+    clearing.insert(Action::Explore, Some(OnAction::new("You look around and see nothing of interest. Exploring to the South and West show more of the ruins. They look like modern day buildings, or the left overs after nature scavanged them. After man abandonded them.".to_string(), None)));
+
+    let mut path = HashMap::new();
+
+    path.insert(Action::Explore, Some(OnAction::new("You look around the path, over at the trees and up at the sky. You explore your surroundings and feel like someone is watching you. Perhaps their up ahead, or behind you, or maybe you're just going crazy.".to_string(), None)));
+
+    let mut river = HashMap::new();
+
+    river.insert(Action::Talk, Some(OnAction::new("You enter the conversation.".to_string(), None)));
+
+    let poet = Person::new("Mysterious Old Man".to_string(), None, Conversation::new("are_you_lost".to_string(), vec![
+        Converse::new("are_you_lost".to_string(), r#"The old man looks at you and asks: "Are you ok? Are you lost?""#.to_string(),
+            Some(vec![Choices::new("Who are you?".to_string(), "who_am_i".to_string())])),
+        Converse::new("who_am_i".to_string(), "Who am I? I am the Poet. Who are you child? Dont speak your name, it sits on my toungue. Where are you from?".to_string(),
+            Some(vec![Choices::new("Not from here ...".to_string(), "follow_me".to_string())])),
+        Converse::new("follow_me".to_string(), "I can see that. We should get you inside before it rains. Follow me. Its a short distance to my house. Come along your safe.".to_string(), None),
+    ]));
+
+    World::new("grassy_clearing".to_string(), vec![
+        Room::new(
+            "grassy_clearing".to_string(),
+            "Grassy Clearing".to_string(),
+            "The sound of birds, the rustling of the wind. The warmth of the sun in the blue sky. The grassy clearing is clear, trees to the east, a small path to the north. South an West contain ruins and broken peices of what appears to be concrete".to_string(),
+            clearing,
+            vec![make_exit(Direction::N, "path".to_string())],
+            GoBack::new(true, None),
+            None,
+        ),
+        Room::new(
+            "path".to_string(),
+            "Path".to_string(),
+            "As you walk up the path the trees around seem to get thicker, taller and the area darker with shade. The path continues to go north.".to_string(),
+            path,
+            vec![make_exit(Direction::N, "river".to_string())],
+            GoBack::new(true, None),
+            None,
+        ),
+        Room::new(
+            "river".to_string(),
+            "River".to_string(),
+            r#"Continuing to follow the path, you come across a creek. The water is softly and quietly moving along its course. A prescence causes you to shudder and turn around. You see an old man standing behind you wearing a fedora red robes leaning on a staff. He looks at you for a moment before saying: "Hello there!""#.to_string(),
+            river,
+            vec![Exit::new(Direction::NONE, None)],
+            GoBack::new(false, Some("There is something preventing you from going back. Is there something to do here?".to_string())),
+            Some(vec![poet]),
+        ),
+    ])
+}
+
+/// Everything about the rooms of a world, a line each, so two worlds can be compared.
+fn summary(world: &World) -> Vec<String> {
+    let mut lines = vec![format!("start: {}", world.start)];
+
+    for room in world.rooms.values() {
+        lines.push(format!("{}: {} - {}", room.id, room.name, room.description));
+        lines.push(format!("{} go back: {} {:?}", room.id, room.go_back.can_go_back, room.go_back.reason));
+
+        for (action, on_action) in &room.actions {
+            lines.push(format!("{} {}: {:?}", room.id, action.name(), on_action.as_ref().map(|on_action| &on_action.on_action)));
+        }
+
+        for exit in &room.exits {
+            lines.push(format!("{} exit: {} to {:?}", room.id, exit.direction.name(), exit.room));
+        }
+
+        for person in room.npcs.iter().flatten() {
+            lines.push(format!("{} person: {}, starts at {}", room.id, person.name, person.conversation.start));
+
+            for node in person.conversation.nodes.values() {
+                lines.push(format!("{} {}: {}", person.name, node.id, node.line));
+
+                for choice in node.choices.iter().flatten() {
+                    lines.push(format!("{} {}: {} -> {}", person.name, node.id, choice.choice, choice.next));
+                }
+            }
+        }
+    }
+
+    lines.sort();
+
+    lines
+}
+
+#[test]
+fn the_adventure_file_builds_the_world_that_was_built_in_rust() {
+    let loaded = dark_harvest_world().unwrap();
+
+    assert_eq!(summary(&loaded), summary(&built_in_rust()));
+}
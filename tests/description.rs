@@ -0,0 +1,33 @@
+use world::description::Description;
+
+#[test]
+fn only_the_text_is_shown_for_an_empty_room() {
+    assert_eq!(Description::new("A bare room.".to_string()).render(), vec!["A bare room."]);
+}
+
+#[test]
+fn fragments_are_added_to_the_text_and_what_is_in_the_room_is_listed() {
+    let description = Description::new("A warm kitchen.".to_string())
+        .with_fragments(vec!["The soup is bubbling.".to_string()])
+        .with_people(vec!["Old Man".to_string(), "Old Woman".to_string()])
+        .with_items(vec!["Key".to_string()])
+        .with_enemies(vec!["Rat".to_string()])
+        .with_exits(vec!["north".to_string(), "up (ladder)".to_string()]);
+
+    assert_eq!(description.render(), vec![
+        "A warm kitchen. The soup is bubbling.",
+        "People here: Old Man, Old Woman",
+        "You see: Key",
+        "Enemies: Rat",
+        "Exits: north, up (ladder)",
+    ]);
+}
+
+#[test]
+fn a_dark_room_hides_its_items() {
+    let description = Description::new("A cellar.".to_string())
+        .with_items(vec!["Candle".to_string()])
+        .dark(true);
+
+    assert_eq!(description.render(), vec!["A cellar.", "It's too dark to make anything out."]);
+}
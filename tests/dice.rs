@@ -0,0 +1,75 @@
+use core::dice::{Dice, Keep};
+use core::rng::Rng;
+
+#[test]
+fn dice_expressions_are_read() {
+    assert_eq!(Dice::parse("3d6").unwrap(), Dice::new(3, 6));
+    assert_eq!(Dice::parse("d20").unwrap(), Dice::new(1, 20));
+    assert_eq!(Dice::parse("1d20+2").unwrap(), Dice::new(1, 20).with_modifier(2));
+    assert_eq!(Dice::parse("1D20 - 1").unwrap(), Dice::new(1, 20).with_modifier(-1));
+    assert_eq!(Dice::parse("4d6kh3").unwrap(), Dice::new(4, 6).keep(Keep::Highest(3)));
+    assert_eq!(Dice::parse("2d20kl1+3").unwrap(), Dice::new(2, 20).keep(Keep::Lowest(1)).with_modifier(3));
+
+    for bad in ["", "6", "3d", "0d6", "3d0", "xd6", "4d6kh5", "4d6kh0", "4d6kx3", "1d20+", "101d6", "1d1001", "1d3000000000", "1d20+1001", "1d20-1001"] {
+        assert!(Dice::parse(bad).is_err(), "{} should not be read", bad);
+    }
+}
+
+#[test]
+fn dice_are_written_the_way_they_are_read() {
+    for expression in ["3d6", "1d20+2", "1d20-1", "4d6kh3", "2d20kl1"] {
+        assert_eq!(Dice::parse(expression).unwrap().to_string(), expression);
+    }
+}
+
+#[test]
+fn rolls_stay_in_range_and_can_roll_the_highest_side() {
+    let mut rng = Rng::new(7);
+    let dice = Dice::parse("1d20").unwrap();
+
+    let rolls: Vec<i32> = (0..1000).map(|_| dice.roll(&mut rng).total).collect();
+
+    assert!(rolls.iter().all(|roll| (1..=20).contains(roll)));
+    assert!(rolls.contains(&1));
+    assert!(rolls.contains(&20));
+}
+
+#[test]
+fn keeping_the_highest_drops_the_lowest() {
+    let mut rng = Rng::new(3);
+
+    for _ in 0..100 {
+        let roll = Dice::parse("4d6kh3+1").unwrap().roll(&mut rng);
+
+        let mut sorted = roll.dice.clone();
+        sorted.sort();
+
+        assert_eq!(roll.dice.len(), 4);
+        assert_eq!(roll.kept_dice().len(), 3);
+        assert_eq!(roll.dropped_dice(), vec![sorted[0]]);
+        assert_eq!(roll.total, sorted[1..].iter().sum::<i32>() + 1);
+    }
+}
+
+#[test]
+fn the_same_seed_rolls_the_same() {
+    let dice = Dice::parse("3d6").unwrap();
+
+    let mut first_rng = Rng::new(11);
+    let mut second_rng = Rng::new(11);
+
+    let first: Vec<i32> = (0..10).map(|_| dice.roll(&mut first_rng).total).collect();
+    let second: Vec<i32> = (0..10).map(|_| dice.roll(&mut second_rng).total).collect();
+
+    assert_eq!(first, second);
+    assert!(first.iter().any(|total| *total != first[0]), "the rolls should carry on, not start again: {:?}", first);
+}
+
+#[test]
+fn the_biggest_dice_roll_without_overflowing() {
+    let mut rng = Rng::new(5);
+
+    let roll = Dice::parse("100d1000+1000").unwrap().roll(&mut rng);
+
+    assert!(roll.total > 1000 && roll.total <= 101_000);
+}
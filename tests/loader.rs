@@ -0,0 +1,194 @@
+use world::loader::{load_world_from_str, Format};
+use core::stat_methods::StatMethod;
+
+#[test]
+fn a_check_is_rejected_where_it_would_roll_every_time_it_is_shown() {
+    let source = r#"
+start = "road"
+
+[[rooms]]
+id = "road"
+name = "Road"
+description = "A quiet road."
+
+[[rooms.fragments]]
+text = "You spot a hidden path."
+conditions = [{ check = "int", dc = 12 }]
+"#;
+
+    let error = load_world_from_str(source, Format::Toml, "road.toml").err().unwrap();
+
+    assert_eq!(error.node, Some("room road".to_string()));
+    assert!(error.message.contains("a fragment can't have a check condition"), "{}", error);
+}
+
+#[test]
+fn enemies_in_a_room_need_different_names() {
+    let source = r#"
+start = "hold"
+
+[[rooms]]
+id = "hold"
+name = "Hold"
+description = "A damp hold."
+
+[[rooms.enemies]]
+name = "Rat"
+hp = 1
+armor_class = 0
+damage = "1d1"
+
+[[rooms.enemies]]
+name = "rat"
+hp = 1
+armor_class = 0
+damage = "1d1"
+"#;
+
+    let error = load_world_from_str(source, Format::Toml, "hold.toml").err().unwrap();
+
+    assert_eq!(error.node, Some("room hold, enemy rat".to_string()));
+    assert!(error.message.contains("already has this name"), "{}", error);
+}
+
+#[test]
+fn an_error_names_the_file_line_and_node_it_is_about() {
+    let source = r#"start = "road"
+
+[[rooms]]
+id = "road"
+name = "Road"
+description = 'A sign points to the "cellar".'
+
+[[rooms]]
+id = "cellar"
+name = "Cellar"
+description = "A cold cellar."
+exits = [{ direction = "up", room = "attic" }]
+"#;
+
+    let error = load_world_from_str(source, Format::Toml, "cellar.toml").err().unwrap();
+
+    assert_eq!(error.file, "cellar.toml");
+    assert_eq!(error.line, Some(9));
+    assert_eq!(error.node, Some("room cellar".to_string()));
+    assert_eq!(error.message, "no room with the id: attic");
+}
+
+#[test]
+fn a_world_can_be_written_in_json() {
+    let source = r#"{
+    "start": "road",
+    "rooms": [
+        { "id": "road", "name": "Road", "description": "A quiet road.", "exits": [{ "direction": "n", "room": "gate" }] },
+        { "id": "gate", "name": "Gate", "description": "A rusted gate." }
+    ]
+}"#;
+
+    let world = load_world_from_str(source, Format::Json, "road.json").unwrap();
+
+    assert_eq!(world.start, "road");
+    assert_eq!(world.rooms["road"].exits[0].room, Some("gate".to_string()));
+    assert_eq!(world.rooms["gate"].description, "A rusted gate.");
+}
+
+#[test]
+fn a_world_can_be_written_in_ron() {
+    let source = r#"(
+    start: "road",
+    rooms: [
+        (id: "road", name: "Road", description: "A quiet road.", exits: [(direction: Some("n"), room: Some("gate"))]),
+        (id: "gate", name: "Gate", description: "A rusted gate."),
+    ],
+)"#;
+
+    let world = load_world_from_str(source, Format::Ron, "road.ron").unwrap();
+
+    assert_eq!(world.start, "road");
+    assert_eq!(world.rooms["road"].exits[0].room, Some("gate".to_string()));
+    assert_eq!(world.rooms["gate"].description, "A rusted gate.");
+}
+
+#[test]
+fn a_json_error_names_the_line_and_node() {
+    let source = r#"{
+    "start": "road",
+    "rooms": [
+        { "id": "road", "name": "Road", "description": "A quiet road.", "actions": [{ "action": "dance" }] }
+    ]
+}"#;
+
+    let error = load_world_from_str(source, Format::Json, "road.json").err().unwrap();
+
+    assert_eq!(error.file, "road.json");
+    assert_eq!(error.line, Some(4));
+    assert_eq!(error.node, Some("room road".to_string()));
+}
+
+#[test]
+fn an_exit_in_a_direction_needs_a_room() {
+    let source = r#"
+start = "road"
+
+[[rooms]]
+id = "road"
+name = "Road"
+description = "A quiet road."
+exits = [{ direction = "n" }]
+"#;
+
+    let error = load_world_from_str(source, Format::Toml, "road.toml").err().unwrap();
+
+    assert_eq!(error.node, Some("room road".to_string()));
+    assert!(error.message.contains("the exit to the north needs a room"), "{}", error);
+
+    let dead_end = source.replace(r#"direction = "n""#, r#"direction = "none""#);
+
+    assert!(load_world_from_str(&dead_end, Format::Toml, "road.toml").is_ok());
+}
+
+#[test]
+fn a_named_exit_needs_a_room() {
+    let source = r#"
+start = "road"
+
+[[rooms]]
+id = "road"
+name = "Road"
+description = "A quiet road."
+exits = [{ name = "gate" }]
+"#;
+
+    let error = load_world_from_str(source, Format::Toml, "road.toml").err().unwrap();
+
+    assert_eq!(error.node, Some("room road".to_string()));
+    assert!(error.message.contains("the exit gate needs a room"), "{}", error);
+
+    let gate = source.replace(r#"name = "gate""#, r#"name = "gate", room = "road""#);
+
+    assert!(load_world_from_str(&gate, Format::Toml, "road.toml").is_ok());
+}
+
+#[test]
+fn an_adventure_can_limit_how_stats_are_made() {
+    let road = r#"
+[[rooms]]
+id = "road"
+name = "Road"
+description = "A quiet road."
+"#;
+
+    let every = load_world_from_str(&format!("start = \"road\"\n{}", road), Format::Toml, "road.toml").unwrap();
+
+    assert_eq!(every.stat_methods, StatMethod::all());
+
+    let limited = load_world_from_str(&format!("start = \"road\"\nstat_methods = [\"roll\", \"point_buy:20\"]\n{}", road), Format::Toml, "road.toml").unwrap();
+
+    assert_eq!(limited.stat_methods, vec![StatMethod::Roll, StatMethod::PointBuy(20)]);
+
+    let error = load_world_from_str(&format!("start = \"road\"\nstat_methods = [\"roll\", \"dream\"]\n{}", road), Format::Toml, "road.toml").err().unwrap();
+
+    assert_eq!(error.node, Some("stat_methods".to_string()));
+    assert_eq!(error.line, Some(2));
+    assert!(error.message.contains("no way to make stats called: dream"), "{}", error);
+}
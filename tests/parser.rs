@@ -0,0 +1,38 @@
+use game::parser::{parse, Command};
+
+fn command(verb: &str, object: &str, preposition: Option<&str>, indirect: &str) -> Command {
+    Command {
+        verb: verb.to_string(),
+        object: object.to_string(),
+        preposition: preposition.map(|preposition| preposition.to_string()),
+        indirect: indirect.to_string(),
+    }
+}
+
+#[test]
+fn filler_words_and_case_are_ignored() {
+    assert_eq!(parse("Take the Rusty Key").unwrap(), command("take", "rusty key", None, ""));
+    assert_eq!(parse("please   look"), Some(command("look", "", None, "")));
+    assert_eq!(parse("the a an"), None);
+}
+
+#[test]
+fn a_preposition_splits_the_object_from_the_indirect_object() {
+    assert_eq!(parse("give the letter to the poet").unwrap(), command("give", "letter", Some("to"), "poet"));
+    assert_eq!(parse("take key from chest").unwrap(), command("take", "key", Some("from"), "chest"));
+    assert_eq!(parse("cast charm on the poet").unwrap().rest(), "charm on poet");
+    assert_eq!(parse("ask the pilgrim about the road").unwrap(), command("ask", "pilgrim", Some("about"), "road"));
+}
+
+#[test]
+fn a_preposition_after_the_verb_belongs_to_the_verb() {
+    assert_eq!(parse("look at the old man").unwrap(), command("look", "old man", None, ""));
+    assert_eq!(parse("steal from the guard").unwrap(), command("steal", "guard", None, ""));
+    assert_eq!(parse("go in").unwrap(), command("go", "in", None, ""));
+}
+
+#[test]
+fn some_verbs_are_two_words() {
+    assert_eq!(parse("pick lock gate").unwrap(), command("pick lock", "gate", None, ""));
+    assert_eq!(parse("pick up the key").unwrap(), command("pick up", "key", None, ""));
+}
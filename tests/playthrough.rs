@@ -0,0 +1,71 @@
+use core::console::MemoryConsole;
+use core::rng::Rng;
+use adventures::dark_harvest::run_dark_harvest;
+use game::create_character;
+
+#[test]
+fn dark_harvest_can_be_played_from_memory() {
+    let mut console = MemoryConsole::new(vec![
+        "Child",
+        "elf",
+        "wizard",
+        "roll",
+        "accept",
+        "go n",
+        "go n",
+        "talk",
+        "1",
+        "1",
+        "quit",
+    ]);
+
+    let output = console.output();
+
+    let character = create_character(&mut console).unwrap();
+
+    assert_eq!(character.name, "Child");
+
+    run_dark_harvest(character, Box::new(console), Rng::new(1));
+
+    assert!(output.contains("==== [Dark Harvest] ==="));
+    assert!(output.contains("Location: Path"));
+    assert!(output.contains("Location: River"));
+    assert!(output.contains("Who am I? I am the Poet."));
+    assert!(output.contains("Follow me."));
+    assert!(output.contains("Really? Ok. Bye."));
+}
+
+#[test]
+fn running_out_of_input_ends_the_game() {
+    let mut console = MemoryConsole::new(vec!["Child", "1", "fighter", "1", "accept", "go n"]);
+
+    let output = console.output();
+
+    let character = create_character(&mut console).unwrap();
+
+    run_dark_harvest(character, Box::new(console), Rng::new(1));
+
+    assert!(output.contains("Location: Path"));
+    assert!(output.text().ends_with("Really? Ok. Bye.\n"));
+}
+
+#[test]
+fn running_out_of_input_while_making_a_character_ends_the_adventure() {
+    let mut console = MemoryConsole::new(vec!["Child", "elf", "wizard"]);
+
+    let output = console.output();
+
+    let character = create_character(&mut console).unwrap();
+
+    run_dark_harvest(character, Box::new(console), Rng::new(1));
+
+    assert!(output.text().ends_with("Bye now!\n"));
+    assert!(!output.contains("==== [Dark Harvest] ==="));
+}
+
+#[test]
+fn quitting_instead_of_giving_a_name_makes_no_character() {
+    let mut console = MemoryConsole::new(vec!["quit"]);
+
+    assert!(create_character(&mut console).is_none());
+}
@@ -0,0 +1,46 @@
+use core::stats::Stat;
+use character::charactersheet::{build_character, find_race, races, Stats, MAX_STAT};
+
+#[test]
+fn race_modifiers_are_applied_to_stats() {
+    let mut stats = Stats::new(10, 10, 10, 10, 10);
+
+    find_race("elf").unwrap().apply(&mut stats);
+
+    assert_eq!(stats.summary(), "str: 10, int: 11, dex: 12, chr: 10, dur: 9");
+}
+
+#[test]
+fn race_modifiers_keep_stats_in_range() {
+    for race in races() {
+        let mut high = Stats::new(MAX_STAT, MAX_STAT, MAX_STAT, MAX_STAT, MAX_STAT);
+        let mut low = Stats::new(1, 1, 1, 1, 1);
+
+        race.apply(&mut high);
+        race.apply(&mut low);
+
+        for stat in Stat::all() {
+            assert!((1..=MAX_STAT).contains(&high.get(stat)), "{} pushed {} out of range", race.name, stat.name());
+            assert!((1..=MAX_STAT).contains(&low.get(stat)), "{} pushed {} out of range", race.name, stat.name());
+        }
+    }
+}
+
+#[test]
+fn traits_come_from_the_characters_race() {
+    let mut character = build_character("Child".to_string());
+
+    assert!(!character.has_trait("darkvision"));
+    assert_eq!(character.skill_bonus(Stat::Chr), 0);
+
+    character.race = find_race("dwarf");
+
+    assert!(character.has_trait("darkvision"));
+    assert!(character.has_trait("Darkvision"));
+
+    character.race = find_race("halfling");
+
+    assert!(!character.has_trait("darkvision"));
+    assert_eq!(character.skill_bonus(Stat::Chr), 2);
+    assert_eq!(character.skill_bonus(Stat::Int), 0);
+}
@@ -0,0 +1,204 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use character::charactersheet::{build_character, find_class, Character, Stats, MAX_STAT};
+use core::console::MemoryConsole;
+use core::stats::Stat;
+use game::Game;
+use game::save::{read_save, slot_path, write_save, Save, SAVE_VERSION};
+use tempfile::tempdir;
+use world::World;
+use world::loader::{load_world_from_str, Format};
+use world::state::Value;
+
+const WORLD: &str = r#"
+start = "road"
+
+[[rooms]]
+id = "road"
+name = "Road"
+description = "A quiet road."
+exits = [{ direction = "n", room = "cellar", door = { name = "gate", closed = true, locked = true } }]
+items = [{ name = "Key", description = "A small iron key." }]
+
+[[rooms.actions]]
+action = "explore"
+on_action = "You find nothing but dust."
+dc_check = 5
+
+[[rooms]]
+id = "cellar"
+name = "Cellar"
+description = "A cold cellar."
+
+[[rooms.enemies]]
+name = "Rat"
+hp = 4
+armor_class = 0
+damage = "1d1"
+"#;
+
+fn world() -> World {
+    load_world_from_str(WORLD, Format::Toml, "save.toml").unwrap()
+}
+
+fn fighter() -> Character {
+    let mut character = build_character("Child".to_string());
+
+    character.class = find_class("fighter");
+    character.hp = character.max_hp();
+
+    character
+}
+
+fn game() -> Game {
+    Game::new(fighter(), world(), Box::new(MemoryConsole::new(vec![]))).with_seed(7)
+}
+
+/// Write the contents straight to the slots file, the way an older game would have.
+fn write_file(directory: &Path, slot: &str, contents: &str) {
+    fs::write(slot_path(directory, slot).unwrap(), contents).unwrap();
+}
+
+#[test]
+fn a_game_comes_back_the_way_it_was_saved() {
+    let mut saved = game();
+
+    let key = saved.world.rooms.get_mut("road").unwrap().items.remove(0);
+    saved.game_character.add_item(key);
+    saved.game_character.damage(3);
+    saved.game_character.xp = 40;
+    saved.state.set("met_the_poet", Value::Bool(true));
+    saved.awarded.insert("check: room road, action explore".to_string());
+    saved.world.rooms.get_mut("road").unwrap().exits[0].door.as_mut().unwrap().locked = false;
+    saved.world.rooms.get_mut("cellar").unwrap().enemies[0].hp = 1;
+    saved.current_room = "cellar".to_string();
+    saved.history = vec!["road".to_string()];
+    saved.rng.gen_range(1, 20);
+
+    let directory = tempdir().unwrap();
+
+    write_save(directory.path(), "round_trip", &Save::from_game(&saved)).unwrap();
+    let save = read_save(directory.path(), "round_trip").unwrap();
+
+    let mut loaded = game();
+    save.restore(&mut loaded);
+
+    assert_eq!(loaded.current_room, "cellar");
+    assert_eq!(loaded.history, vec!["road".to_string()]);
+    assert!(loaded.game_character.has_item("key"));
+    assert!(loaded.world.rooms["road"].items.is_empty());
+    assert_eq!(loaded.game_character.hp, saved.game_character.hp);
+    assert_eq!(loaded.game_character.xp, 40);
+    assert!(loaded.state.flag("met_the_poet"));
+    assert!(loaded.awarded.contains("check: room road, action explore"));
+    assert!(!loaded.world.rooms["road"].exits[0].door.as_ref().unwrap().locked);
+    assert_eq!(loaded.world.rooms["cellar"].enemies[0].hp, 1);
+
+    let rolls: Vec<i32> = (0..10).map(|_| saved.rng.gen_range(1, 20)).collect();
+    let loaded_rolls: Vec<i32> = (0..10).map(|_| loaded.rng.gen_range(1, 20)).collect();
+
+    assert_eq!(loaded_rolls, rolls);
+}
+
+#[test]
+fn older_saves_load_with_the_defaults() {
+    let directory = tempdir().unwrap();
+
+    write_file(directory.path(), "version_2", r#"{
+    "version": 2,
+    "character": {
+        "name": "Child",
+        "stats": null,
+        "class": { "name": "Fighter", "can_cast": false, "can_steal": false, "max_hp": 12 },
+        "race": null
+    },
+    "current_room": "cellar",
+    "state": { "values": { "xp_check_you find nothing but dust.": { "Bool": true }, "met_the_poet": { "Bool": true } } },
+    "rooms": [{ "id": "cellar" }]
+}"#);
+
+    let save = read_save(directory.path(), "version_2").unwrap();
+
+    assert!(save.version < SAVE_VERSION);
+
+    let mut loaded = game();
+    save.restore(&mut loaded);
+
+    // Before version 3 characters had no hp, they come back at full hp.
+    assert_eq!(loaded.game_character.hp, 12);
+    // Before version 4 rooms didn't keep their enemies, so the adventure's are kept.
+    assert_eq!(loaded.world.rooms["cellar"].enemies.len(), 1);
+    // Before version 5 everyone was level 1.
+    assert_eq!(loaded.game_character.level, 1);
+    assert_eq!(loaded.game_character.xp, 0);
+    assert_eq!(loaded.game_character.class.as_ref().unwrap().damage, "1d4");
+    // Before version 6 the xp already given was kept in the story as xp_ flags.
+    assert!(!loaded.state.flag("xp_check_you find nothing but dust."));
+    assert!(loaded.state.flag("met_the_poet"));
+    assert!(loaded.awarded.is_empty());
+}
+
+#[test]
+fn a_save_from_a_newer_game_is_rejected() {
+    let mut save = Save::from_game(&game());
+
+    save.version = SAVE_VERSION + 1;
+
+    let directory = tempdir().unwrap();
+
+    write_save(directory.path(), "newer_version", &save).unwrap();
+    let error = read_save(directory.path(), "newer_version").err().unwrap();
+
+    assert!(error.message.contains("newer version of the game"), "{}", error);
+}
+
+#[test]
+fn slot_names_stay_in_the_save_directory() {
+    let directory = Path::new("saves");
+
+    assert!(slot_path(directory, "../x").is_err());
+    assert!(slot_path(directory, "a/b").is_err());
+    assert!(slot_path(directory, "").is_err());
+    assert_eq!(slot_path(directory, "slot-1_b").unwrap(), PathBuf::from("saves").join("slot-1_b.json"));
+}
+
+#[test]
+fn a_save_in_a_room_that_is_gone_starts_back_at_the_beginning() {
+    let console = MemoryConsole::new(vec![]);
+    let output = console.output();
+
+    let mut save = Save::from_game(&game());
+
+    save.current_room = "attic".to_string();
+    save.history = vec!["road".to_string(), "attic".to_string()];
+
+    let mut loaded = Game::new(fighter(), world(), Box::new(console));
+    save.restore(&mut loaded);
+
+    assert_eq!(loaded.current_room, "road");
+    assert!(loaded.history.is_empty());
+    assert!(output.contains("no longer part of this adventure"));
+}
+
+#[test]
+fn stats_from_an_edited_save_are_kept_in_range() {
+    let mut save = Save::from_game(&game());
+
+    save.character.stats = Some(Stats::new(99, -5, 10, 10, 10));
+
+    let console = MemoryConsole::new(vec!["explore"]);
+    let output = console.output();
+
+    let mut loaded = Game::new(fighter(), world(), Box::new(console)).with_seed(7);
+    save.restore(&mut loaded);
+
+    let stats = loaded.game_character.stats.clone().unwrap();
+
+    assert_eq!(stats.get(Stat::Str), MAX_STAT);
+    assert_eq!(stats.get(Stat::Int), 1);
+
+    loaded.run();
+
+    // Explore rolls with int, which was clamped to 1.
+    assert!(output.contains("(1d20-4: "), "{}", output.text());
+}
@@ -0,0 +1,58 @@
+use core::console::MemoryConsole;
+use core::rng::Rng;
+use character::charactersheet::{build_character, create_stats};
+use character::stat_methods::StatMethod;
+
+fn make_stats(methods: Vec<StatMethod>, input: Vec<&str>) -> (String, String) {
+    let mut console = MemoryConsole::new(input);
+    let output = console.output();
+
+    let character = create_stats(build_character("Child".to_string()), &methods, &mut console, &mut Rng::new(5)).unwrap();
+
+    (character.stats.unwrap().summary(), output.text())
+}
+
+#[test]
+fn the_standard_array_goes_to_the_chosen_stats() {
+    let (stats, _) = make_stats(StatMethod::all(), vec!["array", "dex", "dex", "chr", "int", "str"]);
+
+    assert_eq!(stats, "str: 12, int: 13, dex: 15, chr: 14, dur: 10");
+}
+
+#[test]
+fn point_buy_keeps_to_the_budget() {
+    let (stats, output) = make_stats(vec![StatMethod::PointBuy(10)], vec!["str 15", "str 14", "dex 11", "dex 12", "accept"]);
+
+    assert!(output.contains("You don't have enough points for that."));
+    assert_eq!(stats, "str: 14, int: 8, dex: 11, chr: 8, dur: 8");
+}
+
+#[test]
+fn rolled_values_can_be_given_to_any_stat() {
+    let (stats, output) = make_stats(vec![StatMethod::Manual], vec!["dur", "chr", "dex", "int"]);
+
+    let values: Vec<i32> = output.lines()
+                                 .skip_while(|line| !line.starts_with("Rolled values:"))
+                                 .skip(2)
+                                 .take(5)
+                                 .map(|line| line.split(' ').next().unwrap().parse().unwrap())
+                                 .collect();
+
+    assert_eq!(stats, format!("str: {}, int: {}, dex: {}, chr: {}, dur: {}", values[4], values[3], values[2], values[1], values[0]));
+}
+
+#[test]
+fn the_adventure_decides_which_methods_can_be_picked() {
+    let (stats, output) = make_stats(vec![StatMethod::StandardArray, StatMethod::DropLowest], vec!["roll", "2", "accept"]);
+
+    assert!(output.contains("That's not one of the choices."));
+    assert!(output.contains("4d6kh3"));
+    assert!(!stats.is_empty());
+}
+
+#[test]
+fn running_out_of_input_makes_no_stats() {
+    let mut console = MemoryConsole::new(vec!["array", "dex"]);
+
+    assert!(create_stats(build_character("Child".to_string()), &StatMethod::all(), &mut console, &mut Rng::new(5)).is_none());
+}
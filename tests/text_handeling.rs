@@ -0,0 +1,34 @@
+use core::text_handeling::{closest, edit_distance, is_close, or_list};
+
+#[test]
+fn edit_distance_counts_changes_and_swaps() {
+    assert_eq!(edit_distance("look", "look"), 0);
+    assert_eq!(edit_distance("lok", "look"), 1);
+    assert_eq!(edit_distance("kye", "key"), 1);
+    assert_eq!(edit_distance("Nroth", "north"), 1);
+    assert_eq!(edit_distance("", "go"), 2);
+}
+
+#[test]
+fn short_words_must_be_spelled_right() {
+    assert!(!is_close("go", "no"));
+    assert!(is_close("tlak", "talk"));
+    assert!(!is_close("tk", "talk"));
+    assert!(is_close("inventroy", "inventory"));
+}
+
+#[test]
+fn closest_gives_every_word_that_is_just_as_close() {
+    let words = ["look", "lock", "loot", "take"];
+
+    assert_eq!(closest("lok", &words), vec!["look", "lock"]);
+    assert_eq!(closest("tkae", &words), vec!["take"]);
+    assert!(closest("dance", &words).is_empty());
+}
+
+#[test]
+fn or_list_joins_the_last_word_with_or() {
+    assert_eq!(or_list(&["look".to_string()]), "look");
+    assert_eq!(or_list(&["the old man".to_string(), "the child".to_string()]), "the old man or the child");
+    assert_eq!(or_list(&["a".to_string(), "b".to_string(), "c".to_string()]), "a, b or c");
+}
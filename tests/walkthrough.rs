@@ -0,0 +1,232 @@
+//! Plays every adventure script in `tests/walkthroughs` and checks what the game printed.
+//!
+//! A script is a `.walk` file. It starts with a header naming the adventure file, the seed and
+//! the character, followed by the players input and what we expect to see:
+//!
+//! ```text
+//! # Comments start with a #.
+//! adventure: adventures/data/dark_harvest.toml
+//! seed: 7
+//! name: Child
+//! class: wizard
+//! race: elf
+//! stats: 10 10 10 10 10
+//! hp: 4
+//!
+//! < Location: Grassy Clearing
+//! > go n
+//! < Location: Path
+//! ! Location: River
+//! ```
+//!
+//! `> text` is typed by the player. `< text` must be printed after the last thing typed and
+//! before the next, `! text` must not be. Stats are in the order: str int dex chr dur.
+//!
+//! The race is optional and only gives the character the races traits, the stats are used as
+//! written. Hp is optional too, the character starts with the max hp of their class.
+//!
+//! The game is rolled with the seed, so DC checks come out the same every run.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use core::console::MemoryConsole;
+use character::charactersheet::{build_character, find_class, find_race, Stats};
+use game::Game;
+use world::loader::load_world;
+
+const WALKTHROUGHS: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/walkthroughs");
+
+/// What happens after the player types something, or before they type anything.
+struct Step {
+    input: Option<String>,
+    line: usize,
+    expect: Vec<(usize, String)>,
+    reject: Vec<(usize, String)>,
+}
+
+struct Script {
+    adventure: Option<String>,
+    seed: u64,
+    name: String,
+    class: String,
+    race: Option<String>,
+    stats: Vec<i32>,
+    hp: Option<i32>,
+    steps: Vec<Step>,
+}
+
+fn parse_script(file: &str, source: &str) -> Script {
+    let mut script = Script {
+        adventure: None,
+        seed: 0,
+        name: "Child".to_string(),
+        class: "wizard".to_string(),
+        race: None,
+        stats: vec![10, 10, 10, 10, 10],
+        hp: None,
+        steps: vec![Step { input: None, line: 0, expect: Vec::new(), reject: Vec::new() }],
+    };
+
+    for (index, line) in source.lines().enumerate() {
+        let number = index + 1;
+        let line = line.trim_end();
+
+        if line.trim().is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(input) = line.strip_prefix("> ") {
+            script.steps.push(Step { input: Some(input.to_string()), line: number, expect: Vec::new(), reject: Vec::new() });
+            continue;
+        }
+
+        if let Some(text) = line.strip_prefix("< ") {
+            script.steps.last_mut().unwrap().expect.push((number, text.to_string()));
+            continue;
+        }
+
+        if let Some(text) = line.strip_prefix("! ") {
+            script.steps.last_mut().unwrap().reject.push((number, text.to_string()));
+            continue;
+        }
+
+        let (key, value) = match line.split_once(':') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            None => panic!("{}:{}: expected a header, > input, < expected or ! rejected line.", file, number),
+        };
+
+        match key {
+            "adventure" => script.adventure = Some(value.to_string()),
+            "seed" => script.seed = value.parse().unwrap_or_else(|_| panic!("{}:{}: the seed must be a number.", file, number)),
+            "name" => script.name = value.to_string(),
+            "class" => script.class = value.to_string(),
+            "race" => script.race = Some(value.to_string()),
+            "hp" => script.hp = Some(value.parse().unwrap_or_else(|_| panic!("{}:{}: hp must be a number.", file, number))),
+            "stats" => {
+                script.stats = value.split_whitespace()
+                                    .map(|stat| stat.parse().unwrap_or_else(|_| panic!("{}:{}: stats must be numbers.", file, number)))
+                                    .collect();
+
+                assert!(script.stats.len() == 5, "{}:{}: there must be five stats: str int dex chr dur.", file, number);
+            },
+            _ => panic!("{}:{}: unknown header: {}", file, number, key),
+        }
+    }
+
+    script
+}
+
+/// Play the script and return everything that didn't go as expected.
+fn play(path: &Path) -> Vec<String> {
+    let file = path.file_name().unwrap().to_string_lossy().to_string();
+    let source = fs::read_to_string(path).unwrap();
+    let script = parse_script(&file, &source);
+
+    let adventure = match &script.adventure {
+        Some(adventure) => PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(adventure),
+        None => panic!("{}: the script needs an adventure: header.", file),
+    };
+
+    let world = load_world(&adventure).unwrap_or_else(|e| panic!("{}: {}", file, e));
+
+    let mut character = build_character(script.name.clone());
+    let stats = &script.stats;
+
+    character.class = Some(find_class(&script.class).unwrap_or_else(|| panic!("{}: unknown class: {}", file, script.class)));
+    character.stats = Some(Stats::new(stats[0], stats[1], stats[2], stats[3], stats[4]));
+
+    character.hp = script.hp.unwrap_or_else(|| character.max_hp());
+
+    if let Some(race) = &script.race {
+        character.race = Some(find_race(race).unwrap_or_else(|| panic!("{}: unknown race: {}", file, race)));
+    }
+
+    let inputs: Vec<&str> = script.steps.iter().filter_map(|step| step.input.as_deref()).collect();
+
+    let console = MemoryConsole::new(inputs);
+    let output = console.output();
+
+    // Saves made by a walkthrough are thrown away with it.
+    let saves = tempfile::tempdir().unwrap();
+
+    let mut game = Game::new(character, world, Box::new(console)).with_seed(script.seed).with_save_directory(saves.path().to_path_buf());
+
+    game.run();
+
+    let transcript = output.text();
+
+    // Each input is echoed after its prompt, which splits the transcript into what was printed
+    // after each step.
+    let mut starts = Vec::new();
+    let mut cursor = 0;
+
+    for step in &script.steps {
+        match &step.input {
+            None => starts.push(Some(0)),
+            Some(input) => {
+                let echo = format!("> {}\n", input);
+
+                match transcript[cursor..].find(&echo) {
+                    Some(position) => {
+                        cursor = cursor + position + echo.len();
+                        starts.push(Some(cursor));
+                    },
+                    None => starts.push(None),
+                }
+            },
+        }
+    }
+
+    let mut failures = Vec::new();
+
+    for (index, step) in script.steps.iter().enumerate() {
+        let typed = match &step.input {
+            Some(input) => format!("after > {} (line {})", input, step.line),
+            None => "before any input".to_string(),
+        };
+
+        let start = match starts[index] {
+            Some(start) => start,
+            None => {
+                failures.push(format!("{}:{}: the game stopped reading input {}.", file, step.line, typed));
+                break;
+            },
+        };
+
+        let end = starts[index + 1..].iter()
+                                     .find_map(|start| *start)
+                                     .unwrap_or(transcript.len());
+
+        let printed = &transcript[start..end];
+
+        for (line, text) in &step.expect {
+            if !printed.contains(text.as_str()) {
+                failures.push(format!("{}:{}: expected \"{}\" {}, the game printed:\n{}", file, line, text, typed, printed));
+            }
+        }
+
+        for (line, text) in &step.reject {
+            if printed.contains(text.as_str()) {
+                failures.push(format!("{}:{}: did not expect \"{}\" {}, the game printed:\n{}", file, line, text, typed, printed));
+            }
+        }
+    }
+
+    failures
+}
+
+#[test]
+fn every_walkthrough_plays_as_written() {
+    let mut scripts: Vec<PathBuf> = fs::read_dir(WALKTHROUGHS).unwrap()
+                                                              .map(|entry| entry.unwrap().path())
+                                                              .filter(|path| path.extension().is_some_and(|extension| extension == "walk"))
+                                                              .collect();
+
+    scripts.sort();
+
+    assert!(!scripts.is_empty(), "no walkthroughs found in {}", WALKTHROUGHS);
+
+    let failures: Vec<String> = scripts.iter().flat_map(|script| play(script)).collect();
+
+    assert!(failures.is_empty(), "\n\n{}\n", failures.join("\n\n"));
+}
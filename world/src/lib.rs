@@ -5,43 +5,150 @@ pub use crate::world::actions;
 pub use crate::world::conversation;
 pub use crate::world::room;
 pub use crate::world::person;
+pub use crate::world::item;
+pub use crate::world::condition;
+pub use crate::world::state;
+pub use crate::world::effect;
+pub use crate::world::context;
+pub use crate::world::enemy;
+pub use crate::world::level;
+pub use crate::world::verb;
+pub use crate::world::description;
+pub use crate::world::loader;
 
+use std::collections::HashMap;
 use crate::world::room::Room;
+use crate::world::person::Person;
+use crate::world::level::{Level, Experience};
+use crate::world::verb::Verb;
+use core::stat_methods::StatMethod;
 
-/// The core world sturcture that contains the room.
+/// The core world sturcture that contains the rooms.
 ///
-/// The room is the base room that then contains exits in to other rooms
-/// that can then branch off.
+/// Rooms are kept by their id and exits point to the id of the room they lead to,
+/// so rooms can link back to each other, loop around or share a hub.
 ///
-/// To get around moving back to the previous room the world will contain
-/// a previous room and a current_room, which is the room you were in and the room you are in
-/// respectivly.
-#[derive(Debug)]
+/// Because every room lives here exactly once, changes made to a room (such as an
+/// item being picked up) are still there when the player comes back to it.
+///
+/// The world also holds the adventures level table, how much experience it gives out and
+/// the commands it adds to the game and the ways the player can make their stats.
+#[derive(Clone, Debug)]
 pub struct World {
-    pub room: Room,
-    pub current_room: Option<Room>,
-    pub previous_room: Option<Room>,
+    pub start: String,
+    pub rooms: HashMap<String, Room>,
+    pub levels: Vec<Level>,
+    pub experience: Experience,
+    pub verbs: Vec<Verb>,
+    pub stat_methods: Vec<StatMethod>,
 }
 
 impl World {
 
-    /// Create a new world with the base room.
-    pub fn new(room: Room) -> Self{
+    /// Create a new world from its rooms and the id of the room the player starts in.
+    pub fn new(start: String, rooms: Vec<Room>) -> Self {
+        let mut room_map = HashMap::new();
+
+        for room in rooms {
+            room_map.insert(room.id.clone(), room);
+        }
+
         World {
-            room: room,
-            current_room: None,
-            previous_room: None,
+            start: start,
+            rooms: room_map,
+            levels: Vec::new(),
+            experience: Experience::default(),
+            verbs: Vec::new(),
+            stat_methods: StatMethod::all(),
         }
     }
 
+    /// Set the commands the adventure adds to the game.
+    pub fn with_verbs(mut self, verbs: Vec<Verb>) -> Self {
+        self.verbs = verbs;
+
+        return self;
+    }
+
+    /// Set the ways the player can make their stats, every method is allowed until this is set.
+    pub fn with_stat_methods(mut self, stat_methods: Vec<StatMethod>) -> Self {
+        self.stat_methods = stat_methods;
+
+        return self;
+    }
+
+    /// Find the command the adventure added for the word, by its name or an alias.
+    pub fn find_verb(&self, word: &str) -> Option<&Verb> {
+        return self.verbs.iter().find(|verb| verb.matches(word));
+    }
+
+    /// Set the level table, lowest level first.
+    pub fn with_levels(mut self, levels: Vec<Level>) -> Self {
+        self.levels = levels;
+
+        return self;
+    }
+
+    /// Set how much experience passing a check and finishing a conversation gives.
+    pub fn with_experience(mut self, experience: Experience) -> Self {
+        self.experience = experience;
+
+        return self;
+    }
+
+    /// The level after the given one, if the adventure has one.
+    pub fn next_level(&self, level: i32) -> Option<&Level> {
+        return self.levels.iter().find(|next| next.level == level + 1);
+    }
+
+    /// The level a class ability unlocks at, if the level table locks it at all.
+    pub fn unlocked_at(&self, ability: &str) -> Option<i32> {
+        return self.levels.iter().find(|level| level.unlocks.iter().any(|unlock| unlock == ability)).map(|level| level.level);
+    }
+
     /// Gets the base room.
     pub fn get_base_room(&self) -> &Room {
-        return &self.room
+        return self.room(&self.start);
+    }
+
+    /// Gets a room by its id.
+    ///
+    /// Exits are checked when the world is built, so asking for a room that doesn't
+    /// exist is a bug and we panic.
+    pub fn room(&self, id: &str) -> &Room {
+        match self.rooms.get(id) {
+            Some(room) => room,
+            None => panic!("There is no room with the id: {}", id),
+        }
     }
 
-    pub fn set_current_room(mut self, current_room: Room) -> World {
-        self.current_room = Some(current_room);
+    /// Find a person by name, looking in the given room first and then every other room.
+    pub fn find_person_mut(&mut self, room: &str, name: &str) -> Option<&mut Person> {
+        let name = name.to_lowercase();
 
-        return self
+        let mut ids: Vec<String> = vec![room.to_string()];
+
+        ids.extend(self.rooms.keys().filter(|id| id.as_str() != room).cloned());
+
+        for id in ids {
+            let found = match &self.rooms[&id].npcs {
+                Some(people) => people.iter().position(|person| person.name.to_lowercase() == name),
+                None => None,
+            };
+
+            if let Some(index) = found {
+                return Some(&mut self.room_mut(&id).npcs.as_mut().unwrap()[index]);
+            }
+        }
+
+        return None;
+    }
+
+    /// Gets a room by its id so it can be changed.
+    pub fn room_mut(&mut self, id: &str) -> &mut Room {
+        match self.rooms.get_mut(id) {
+            Some(room) => room,
+            None => panic!("There is no room with the id: {}", id),
+        }
     }
 }
@@ -1,35 +1,27 @@
-use std::io;
-use std::io::Write;
 use std::vec::Vec;
 use std::collections::HashMap;
+use core::console::Console;
 use core::text_handeling::unwrap_str;
-use crate::world::conversation::{Converse, Choices};
+use crate::world::conversation::Choices;
+use crate::world::context::Context;
 use menu::menu_system::{parse_quit};
 
-pub fn process(options: HashMap<i32, String>, choices: Vec<Choices>) -> Option<Converse> {
+pub fn process(options: HashMap<i32, String>, choices: Vec<Choices>, console: &mut dyn Console) -> Option<Choices> {
 
     let mut done: bool = false;
 
-    let mut choice_selection: Option<Converse> = None;
+    let mut choice_selection: Option<Choices> = None;
 
     while !done {
-        print!("> ");
-
-        io::stdout().flush().expect("Error flushing stdout!");
-
-        let mut input = String::new();
-
-        io::stdin().read_line(&mut input)
-                   .expect("Error reading stdin!");
-
-        if input.ends_with('\n') {
-           input.pop();
-        }
+        let input = match console.prompt() {
+            Some(input) => input,
+            None => return None,
+        };
 
         let words: Vec<&str> = input.split_whitespace().collect();
 
         if words.is_empty() {
-            println!("Invalid input.");
+            console.println("Invalid input.");
         } else {
             let mut result: Vec<String> = Vec::new();
 
@@ -37,11 +29,11 @@ pub fn process(options: HashMap<i32, String>, choices: Vec<Choices>) -> Option<C
                 result.push(word.to_string());
             }
 
-            if parse_quit(result.clone()) {
+            if parse_quit(result.clone(), console) {
                 done = true;
             }
 
-            choice_selection = parse_choice_input(result.clone(), options.clone(), choices.clone());
+            choice_selection = parse_choice_input(result.clone(), options.clone(), choices.clone(), console);
 
             if choice_selection.is_some() {
                 done = true;
@@ -58,14 +50,21 @@ pub fn conversation_menu(choices: Vec<Choices>) -> HashMap<i32, String> {
     let mut count: i32 = 1;
 
     for choice in &choices {
-        options.insert(count, choice.clone().choice);
+        options.insert(count, choice.label());
         count = count + 1;
     }
 
     return options;
 }
 
-fn parse_choice_input(words: Vec<String>, options: HashMap<i32, String>, choices: Vec<Choices>) -> Option<Converse> {
+/// The choices the player can currently pick, in order.
+///
+/// Choices whose conditions don't pass are left out of the menu.
+pub fn available_choices(choices: Vec<Choices>, context: &mut dyn Context) -> Vec<Choices> {
+    return choices.into_iter().filter(|choice| context.check_all(&choice.conditions)).collect();
+}
+
+fn parse_choice_input(words: Vec<String>, options: HashMap<i32, String>, choices: Vec<Choices>, console: &mut dyn Console) -> Option<Choices> {
     let mut words = words.iter();
 
     let input = unwrap_str(words.next());
@@ -75,9 +74,9 @@ fn parse_choice_input(words: Vec<String>, options: HashMap<i32, String>, choices
             if options.contains_key(&n) {
                 // You can't have a choice that leads to no convo. So if this then
                 // explodses thats on you for not supplying a a conversdation to a choice option
-                return Some(choices[n as usize - 1].next.clone());
+                return Some(choices[n as usize - 1].clone());
             } else {
-                println!("Not a valid choice.");
+                console.println("Not a valid choice.");
 
                 return None;
             }
@@ -85,11 +84,11 @@ fn parse_choice_input(words: Vec<String>, options: HashMap<i32, String>, choices
         Err(_e) => {
             match input {
                 "quit" | "q" | "exit" => {
-                    println!("Conversation over. You can talk again or do other actions in the room. Type help for more information.");
+                    console.println("Conversation over. You can talk again or do other actions in the room. Type help for more information.");
                     return None;
                 },
                 _ => {
-                    println!("invalid input");
+                    console.println("invalid input");
                     return None;
                 }
             }
@@ -2,3 +2,13 @@ pub mod actions;
 pub mod room;
 pub mod conversation;
 pub mod person;
+pub mod loader;
+pub mod item;
+pub mod condition;
+pub mod state;
+pub mod effect;
+pub mod context;
+pub mod enemy;
+pub mod level;
+pub mod verb;
+pub mod description;
@@ -1,7 +1,67 @@
+use std::vec::Vec;
+use core::console::Console;
+use core::dice::Dice;
+use crate::world::condition::Condition;
+use crate::world::context::Context;
+use crate::world::effect::Effect;
+
 /// Acceptable Actions a player can take.
+///
+/// Look, Explore and Talk can be done by anyone. The rest are class abilities:
+///
+/// - Cast: casting the named spell, for Wizards.
+/// - Steal and PickLock: for Thieves.
+/// - Force and Intimidate: for Fighters.
+///
+/// Give is the player handing a person the named item, anyone can do it.
+///
+/// Custom is a command the adventure added, see: Verb.
 #[derive(Clone, PartialEq, Eq, Hash, Debug)]
 pub enum Action {
-    Look, Explore, NONE, Talk
+    Look, Explore, NONE, Talk,
+    Cast(String), Steal, PickLock, Force, Intimidate,
+    Give(String), Custom(String),
+}
+
+impl Action {
+
+    /// Get an action from how adventures write it, for example: look, pick lock, cast light or
+    /// give letter.
+    pub fn from_name(name: &str) -> Option<Action> {
+        let name = name.to_lowercase();
+        let words: Vec<&str> = name.split_whitespace().collect();
+
+        match words.as_slice() {
+            ["look"] => Some(Action::Look),
+            ["explore"] => Some(Action::Explore),
+            ["talk"] => Some(Action::Talk),
+            ["none"] => Some(Action::NONE),
+            ["cast", spell @ ..] if !spell.is_empty() => Some(Action::Cast(spell.join(" "))),
+            ["steal"] => Some(Action::Steal),
+            ["pick", "lock"] | ["pick_lock"] => Some(Action::PickLock),
+            ["force"] => Some(Action::Force),
+            ["intimidate"] => Some(Action::Intimidate),
+            ["give", item @ ..] if !item.is_empty() => Some(Action::Give(item.join(" "))),
+            _ => None,
+        }
+    }
+
+    /// The name of the action as shown to the player.
+    pub fn name(&self) -> String {
+        match self {
+            Action::Look => "look".to_string(),
+            Action::Explore => "explore".to_string(),
+            Action::Talk => "talk".to_string(),
+            Action::NONE => "nothing".to_string(),
+            Action::Cast(spell) => format!("cast {}", spell),
+            Action::Steal => "steal".to_string(),
+            Action::PickLock => "pick lock".to_string(),
+            Action::Force => "force".to_string(),
+            Action::Intimidate => "intimidate".to_string(),
+            Action::Give(item) => format!("give {}", item),
+            Action::Custom(verb) => verb.clone(),
+        }
+    }
 }
 
 /// Do something on action
@@ -12,10 +72,21 @@ pub enum Action {
 /// the action is only successful if the dc_check passes.
 ///
 /// If the Action does not have a dc_check then we should just do the on_action.
+///
+/// The action can also depend on the story so far. When the conditions don't pass the player
+/// is told the failure instead. When the action is successful its effects are applied, for
+/// example setting a flag so the room remembers it was searched.
+///
+/// The key names the action, for example: room cellar, action explore, so the experience for
+/// passing its check is only given once. Actions made with out a key are known by their text.
 #[derive(Clone, Debug)]
 pub struct OnAction {
     pub on_action: String,
     pub dc_check: Option<i32>,
+    pub conditions: Vec<Condition>,
+    pub failure: Option<String>,
+    pub effects: Vec<Effect>,
+    pub key: Option<String>,
 }
 
 impl OnAction {
@@ -25,11 +96,76 @@ impl OnAction {
         OnAction {
             on_action: on_action,
             dc_check: dc_check,
+            conditions: Vec::new(),
+            failure: None,
+            effects: Vec::new(),
+            key: None,
         }
     }
 
+    /// Set the key that names the action.
+    pub fn with_key(mut self, key: String) -> Self {
+        self.key = Some(key);
+
+        return self;
+    }
+
+    /// Set the conditions that must pass to do the action and what the player is told when they don't.
+    pub fn with_conditions(mut self, conditions: Vec<Condition>, failure: Option<String>) -> Self {
+        self.conditions = conditions;
+        self.failure = failure;
+
+        return self;
+    }
+
+    /// Set the effects of doing the action.
+    pub fn with_effects(mut self, effects: Vec<Effect>) -> Self {
+        self.effects = effects;
+
+        return self;
+    }
+
     /// Do the action.
-    pub fn do_action(&mut self) {
-        println!("{}", self.on_action);
+    pub fn do_action(&mut self, console: &mut dyn Console) {
+        console.println(&self.on_action);
+    }
+
+    /// Check the conditions, roll the dc_check with the bonus and, when that all passes, do the
+    /// action and apply its effects.
+    ///
+    /// Returns if the action was done. Passing the dc_check, or a check in the conditions, gives
+    /// the player experience the first time.
+    pub fn perform(&self, bonus: i32, context: &mut dyn Context) -> bool {
+        if !context.check_all(&self.conditions) {
+            match &self.failure {
+                Some(failure) => context.console().println(failure),
+                None => context.console().println("Cannot do that action in this area."),
+            }
+
+            return false;
+        }
+
+        if let Some(dc_check) = self.dc_check {
+            let roll = Dice::new(1, 20).with_modifier(bonus).roll(context.rng());
+
+            if roll.total > dc_check {
+                context.console().println(&format!("Upon your roll of a: {} ({})", roll.total, roll.breakdown()));
+            } else {
+                context.console().println(&format!("Failed to do the action. You failed the DC check: {} ({}). You can try again.", roll.total, roll.breakdown()));
+                return false;
+            }
+        }
+
+        self.clone().do_action(context.console());
+
+        context.apply_all(&self.effects);
+
+        let checked = self.dc_check.is_some() || self.conditions.iter().any(|condition| matches!(condition, Condition::StatCheck { .. }));
+
+        if checked {
+            context.passed_check(self.key.as_ref().unwrap_or(&self.on_action));
+        }
+
+        return true;
     }
 }
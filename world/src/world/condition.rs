@@ -0,0 +1,37 @@
+use core::stats::Stat;
+use crate::world::state::Value;
+
+/// Something that has to be true before the player can do something.
+///
+/// The world only describes conditions, the game decides if they pass as it
+/// knows about the character and the story so far.
+///
+/// - HasItem: the player is carrying an item with this name or alias.
+/// - Flag: a value in the story state is set.
+/// - NotFlag: a value in the story state is not set.
+/// - Equals: a value in the story state is equal to the value.
+/// - AtLeast: a number in the story state is at least the value.
+/// - StatCheck: the player rolls against a DC using the stat bonus for the stat. The roll is made
+///   every time the condition is checked, so it is only for what the player does, not for
+///   descriptions, fragments, choices or hidden exits, which are checked every time they are shown.
+/// - MinStat: the players stat is at least the value, no roll is made.
+/// - Class: the player is of the class with this name.
+/// - CanCast, CanSteal and CanForce: the players class can cast, steal or force.
+/// - Trait: the players race has the trait with this name, for example: darkvision.
+/// - Disposition: how the NPC with this name feels about the player is at least the value.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Condition {
+    HasItem(String),
+    Flag(String),
+    NotFlag(String),
+    Equals { key: String, value: Value },
+    AtLeast { key: String, value: i32 },
+    StatCheck { stat: Stat, dc: i32 },
+    MinStat { stat: Stat, value: i32 },
+    Class(String),
+    CanCast,
+    CanSteal,
+    CanForce,
+    Trait(String),
+    Disposition { npc: String, value: i32 },
+}
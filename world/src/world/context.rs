@@ -0,0 +1,48 @@
+use std::vec::Vec;
+use core::console::Console;
+use core::rng::Rng;
+use crate::world::condition::Condition;
+use crate::world::effect::Effect;
+
+/// What the world needs from the game.
+///
+/// Rooms, actions and conversations only describe their conditions and effects. The
+/// game knows about the character and the story so far, so it is the one that checks
+/// and applies them.
+///
+/// The game also owns the console and the random number generator, so anything in the world
+/// that talks to the player or rolls dice does so through the context.
+pub trait Context {
+
+    /// The console to talk to the player through.
+    fn console(&mut self) -> &mut dyn Console;
+
+    /// The random number generator to roll with.
+    fn rng(&mut self) -> &mut Rng;
+
+    /// Does the condition pass?
+    fn check(&mut self, condition: &Condition) -> bool;
+
+    /// Apply the effect.
+    fn apply(&mut self, effect: &Effect);
+
+    /// The player passed a DC check, so they get experience for it. The key names the check, for
+    /// example the action it was for, so passing the same check again gives nothing.
+    fn passed_check(&mut self, key: &str);
+
+    /// Has the player been defeated? Anything that carries on after applying effects, such as a
+    /// conversation, should stop when they have.
+    fn is_defeated(&mut self) -> bool;
+
+    /// Do all of the conditions pass?
+    fn check_all(&mut self, conditions: &Vec<Condition>) -> bool {
+        return conditions.iter().all(|condition| self.check(condition));
+    }
+
+    /// Apply all of the effects, in order.
+    fn apply_all(&mut self, effects: &Vec<Effect>) {
+        for effect in effects {
+            self.apply(effect);
+        }
+    }
+}
@@ -1,72 +1,130 @@
 use std::vec::Vec;
+use std::collections::HashMap;
+use core::stats::Stat;
 use menu::menu_system::display_menu;
-use crate::menu_system::conversation_menu::{conversation_menu, process};
+use crate::menu_system::conversation_menu::{available_choices, conversation_menu, process};
+use crate::world::condition::Condition;
+use crate::world::effect::Effect;
+use crate::world::context::Context;
 
-/// Converseations consisting of a line and possible choices.
+/// A conversation with someone, stored as a graph of nodes.
 ///
-/// The line is what the NPC sais. The choices are a Option of vector choices.
-/// each choice also has a choice, this is for the player to see and a optional conversation (Converse)
-/// struct called next.
-///
-/// This is considered, much like the way rooms are built, to be a top down recursive struct
-/// that is traversed until the final Converse with no choice is reached.
+/// Each node (Converse) has an id, and each choice points at the id of the node
+/// it leads to. Because choices point at ids rather than holding the next node, a choice can
+/// lead back to an earlier node, like a list of topics the player can return to, and the
+/// same answer can be reached from more than one place.
 ///
 /// A typical conversation structure looks as such:
 ///
-///             Converse
-///                | - Line that starts conversation
-///   Vec<Choices> - choice, next (optional converse) - TODO: Make non optional
-///                |
-///    -------------------------
-///     |          |        |
-///  converse   converse  converse
+///               start (id)
+///                   |
+///     -----------> hub <-----------------
+///     |        /     |      \           |
+///     |   choice  choice  choice        |
+///     |      |       |       |          |
+///     ---- node    node    node -- "Ask about something else"
+///                    |
+///                  goodbye (no choices)
 ///
-/// When process_conversation is called, we recurisvely call the process_conversation
-/// until a Converse doesnt have a choice. All Choices on a Converse object are Optional.
+/// The conversation keeps going until the player reaches a node with no choices,
+/// or leaves by typing q, quit or exit.
 #[derive(Clone, Debug)]
-pub struct Converse {
-    pub line: String,
-    pub choices: Option<Vec<Choices>>,
+pub struct Conversation {
+    pub start: String,
+    pub nodes: HashMap<String, Converse>,
 }
 
-impl Converse {
+impl Conversation {
 
-    /// Create a new instance of Converse
-    pub fn new(line: String, choices: Option<Vec<Choices>>) -> Self {
-        Converse {
-            line: line,
-            choices: choices,
+    /// Create a new conversation starting at the node with the start id.
+    pub fn new(start: String, nodes: Vec<Converse>) -> Self {
+        Conversation {
+            start: start,
+            nodes: nodes.into_iter().map(|node| (node.id.clone(), node)).collect(),
         }
     }
 
+    /// Get the node with the id.
+    pub fn node(&self, id: &str) -> Option<&Converse> {
+        return self.nodes.get(id);
+    }
+
     /// Process the conversation.
     ///
-    /// Assuming a conversation has some choices we will loop ove the choices,
-    /// allowing the user to leave the conversation at any point by typing q, quit, exit.
+    /// Starts at the start node and shows its line. Then, for as long as the current node
+    /// has choices, the player picks one, its effects are applied and we move to the node
+    /// it points at.
     ///
-    /// This method is recurisve until the final conversation struct is found with no choices.
-    /// Then we return to the main loop.
-    pub fn process_conversation(&mut self) {
+    /// Only the choices whose conditions pass are shown. When none are left, the player
+    /// types q, quit or exit, or the player is defeated, we return to the main loop.
+    ///
+    /// Returns true when the conversation got to its end, rather than the player leaving it.
+    pub fn process_conversation(&self, context: &mut dyn Context) -> bool {
+        let mut current = match self.node(&self.start) {
+            Some(node) => node,
+            None => return false,
+        };
 
-        if !self.choices.clone().is_some() {
-            println!("\nWhat do you do? (type help for commands)");
-            return;
-        }
+        context.console().println(&format!("\n{}", current.line));
+
+        loop {
+            let choices = match &current.choices {
+                Some(choices) => available_choices(choices.clone(), context),
+                None => Vec::new(),
+            };
+
+            let mut options = conversation_menu(choices.clone());
+
+            if options.is_empty() {
+                context.console().println("\nWhat do you do? (type help for commands)");
+                return true;
+            }
+
+            display_menu(&mut options, context.console());
 
-        let choices = self.choices.clone().unwrap();
+            // The user might have left the conversation from the menu,
+            // in which case there is no choice and we are done.
+            let choice = match process(options, choices, context.console()) {
+                Some(choice) => choice,
+                None => return false,
+            };
 
-        let mut options = conversation_menu(choices.clone());
+            context.apply_all(&choice.effects);
 
-        display_menu(&mut options);
+            current = match self.node(&choice.next_node(context)) {
+                Some(node) => node,
+                None => return false,
+            };
 
-        let next = process(options, choices.clone());
+            context.console().println(&format!("\n{}", current.line));
 
-        // The user might have exited from a sub menu,
-        // such as a choice. This would cancel the whole: talk
-        // command making next now be None and causing a panic.
-        if next.is_some() {
-            println!("\n{}", next.clone().unwrap().line);
-            next.clone().unwrap().process_conversation();
+            if context.is_defeated() {
+                return false;
+            }
+        }
+    }
+}
+
+/// A single node in a conversation, consisting of an id, a line and possible choices.
+///
+/// The line is what the NPC says. The choices are what the player can say back, each one
+/// pointing at the id of the node that responds to it. A node with no choices ends
+/// the conversation.
+#[derive(Clone, Debug)]
+pub struct Converse {
+    pub id: String,
+    pub line: String,
+    pub choices: Option<Vec<Choices>>,
+}
+
+impl Converse {
+
+    /// Create a new instance of Converse
+    pub fn new(id: String, line: String, choices: Option<Vec<Choices>>) -> Self {
+        Converse {
+            id: id,
+            line: line,
+            choices: choices,
         }
     }
 }
@@ -76,21 +134,117 @@ impl Converse {
 /// Conversations can usually have choices which are stored in a vector.
 /// each choice has a string which is what the player sees in a list of choices.
 ///
-/// The next aspect of the struct contiues the conversation and should be a response to
+/// Next is the id of the node that continues the conversation and should be a response to
 /// the selected choice.
+///
+/// A choice is only shown when its conditions pass, and its effects are applied when the
+/// player picks it.
+///
+/// A choice can also have a skill check, for example CHR to persuade someone. Then next is
+/// where the conversation goes when the player passes the check. The key names the choice,
+/// for example: room river, person poet, node start, choice 1, so passing the check only gives
+/// experience once. Choices made with out a key are known by their text.
 #[derive(Clone, Debug)]
 pub struct Choices {
     pub choice: String,
-    pub next: Converse,
+    pub next: String,
+    pub conditions: Vec<Condition>,
+    pub effects: Vec<Effect>,
+    pub check: Option<SkillCheck>,
+    pub key: Option<String>,
 }
 
 impl Choices {
 
     /// Create a new choice
-    pub fn new(choice: String, next: Converse) -> Self {
+    pub fn new(choice: String, next: String) -> Self {
         Choices {
             choice: choice,
             next: next,
+            conditions: Vec::new(),
+            effects: Vec::new(),
+            check: None,
+            key: None,
+        }
+    }
+
+    /// Set the key that names the choice.
+    pub fn with_key(mut self, key: String) -> Self {
+        self.key = Some(key);
+
+        return self;
+    }
+
+    /// Set the conditions that must pass for the choice to be shown.
+    pub fn with_conditions(mut self, conditions: Vec<Condition>) -> Self {
+        self.conditions = conditions;
+
+        return self;
+    }
+
+    /// Set the effects of picking the choice.
+    pub fn with_effects(mut self, effects: Vec<Effect>) -> Self {
+        self.effects = effects;
+
+        return self;
+    }
+
+    /// Set the skill check the player has to pass when they pick the choice.
+    pub fn with_check(mut self, check: SkillCheck) -> Self {
+        self.check = Some(check);
+
+        return self;
+    }
+
+    /// The choice as the player sees it in the menu.
+    ///
+    /// Choices with a skill check name the stat being rolled.
+    pub fn label(&self) -> String {
+        match &self.check {
+            Some(check) => format!("[{} check] {}", check.stat.name().to_uppercase(), self.choice),
+            None => self.choice.clone(),
+        }
+    }
+
+    /// The id of the node the conversation moves to.
+    ///
+    /// When there is a skill check the player rolls for it here, which decides if we go to
+    /// next or to the failure node. Passing it gives experience the first time.
+    pub fn next_node(&self, context: &mut dyn Context) -> String {
+        match &self.check {
+            Some(check) => {
+                if context.check(&Condition::StatCheck { stat: check.stat, dc: check.dc }) {
+                    context.passed_check(self.key.as_ref().unwrap_or(&self.choice));
+
+                    return self.next.clone();
+                }
+
+                return check.failure.clone();
+            },
+            None => return self.next.clone(),
+        }
+    }
+}
+
+/// A skill check on a conversation choice.
+///
+/// The player rolls against the dc with their bonus for the stat, the same way they do for
+/// actions in a room. Failure is the id of the node the conversation moves to when they fail.
+#[derive(Clone, Debug)]
+pub struct SkillCheck {
+    pub stat: Stat,
+    pub dc: i32,
+    pub failure: String,
+}
+
+impl SkillCheck {
+
+    /// Create a new skill check.
+    pub fn new(stat: Stat, dc: i32, failure: String) -> Self {
+        SkillCheck {
+            stat: stat,
+            dc: dc,
+            failure: failure,
         }
     }
 }
@@ -0,0 +1,119 @@
+use std::vec::Vec;
+
+/// Everything the player is told about a room when they walk in or look around.
+///
+/// - text: the description of the room, see Room::describe.
+/// - fragments: extra sentences added to the end of the text, for example: "The gate stands open."
+/// - people, items and enemies: the names of what the player can see in the room.
+/// - exits: how the exits the player can see are shown, see Exit::label.
+/// - dark: the player can't see, so they are told so instead of what items are in the room.
+///
+/// Rooms put together a description with Room::description, the game then shows it with render.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Description {
+    pub text: String,
+    pub fragments: Vec<String>,
+    pub people: Vec<String>,
+    pub items: Vec<String>,
+    pub enemies: Vec<String>,
+    pub exits: Vec<String>,
+    pub dark: bool,
+}
+
+impl Description {
+
+    /// Create a description that is only the text.
+    pub fn new(text: String) -> Self {
+        Description {
+            text: text,
+            fragments: Vec::new(),
+            people: Vec::new(),
+            items: Vec::new(),
+            enemies: Vec::new(),
+            exits: Vec::new(),
+            dark: false,
+        }
+    }
+
+    /// Set the sentences added to the end of the text.
+    pub fn with_fragments(mut self, fragments: Vec<String>) -> Self {
+        self.fragments = fragments;
+
+        return self;
+    }
+
+    /// Set the names of the people in the room.
+    pub fn with_people(mut self, people: Vec<String>) -> Self {
+        self.people = people;
+
+        return self;
+    }
+
+    /// Set the names of the items in the room.
+    pub fn with_items(mut self, items: Vec<String>) -> Self {
+        self.items = items;
+
+        return self;
+    }
+
+    /// Set the names of the enemies in the room.
+    pub fn with_enemies(mut self, enemies: Vec<String>) -> Self {
+        self.enemies = enemies;
+
+        return self;
+    }
+
+    /// Set the exits, as they are shown to the player.
+    pub fn with_exits(mut self, exits: Vec<String>) -> Self {
+        self.exits = exits;
+
+        return self;
+    }
+
+    /// Make the description one for a room the player can't see in.
+    pub fn dark(mut self, dark: bool) -> Self {
+        self.dark = dark;
+
+        return self;
+    }
+
+    /// The description as it is shown to the player, a paragraph at a time. Anything the room
+    /// doesn't have is left out.
+    ///
+    /// ```text
+    /// A warm kitchen. The soup is bubbling.
+    ///
+    /// People here: Old Man, Old Woman
+    ///
+    /// You see: Key
+    ///
+    /// Exits: north, up (ladder)
+    /// ```
+    pub fn render(&self) -> Vec<String> {
+        let mut text = vec![self.text.trim().to_string()];
+
+        text.extend(self.fragments.iter().map(|fragment| fragment.trim().to_string()));
+
+        let mut paragraphs = vec![text.join(" ")];
+
+        if !self.people.is_empty() {
+            paragraphs.push(format!("People here: {}", self.people.join(", ")));
+        }
+
+        if self.dark {
+            paragraphs.push("It's too dark to make anything out.".to_string());
+        } else if !self.items.is_empty() {
+            paragraphs.push(format!("You see: {}", self.items.join(", ")));
+        }
+
+        if !self.enemies.is_empty() {
+            paragraphs.push(format!("Enemies: {}", self.enemies.join(", ")));
+        }
+
+        if !self.exits.is_empty() {
+            paragraphs.push(format!("Exits: {}", self.exits.join(", ")));
+        }
+
+        return paragraphs;
+    }
+}
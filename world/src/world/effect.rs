@@ -0,0 +1,30 @@
+use core::dice::Dice;
+use crate::world::state::Value;
+use crate::world::item::Item;
+
+/// Something that happens as a result of what the player did.
+///
+/// Like conditions, the world only describes effects and the game applies them.
+///
+/// - Set: set a value in the story state, a flag is just a value set to true.
+/// - Add: add to a number in the story state.
+/// - GiveItem: the player is given the item.
+/// - TakeItem: the item with this name or alias is taken from the player, if they have it.
+/// - Disposition: change how the NPC with this name feels about the player.
+/// - Damage: roll the dice and take that many hit points from the player.
+/// - Heal: roll the dice and give the player back that many hit points, up to their max.
+/// - Fight: start a fight with the enemy with this name in the room.
+/// - Xp: give the player experience, for example for finishing a quest. The key names where the
+///   xp comes from, so it is only given once however many times the effect is applied.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Effect {
+    Set { key: String, value: Value },
+    Add { key: String, amount: i32 },
+    GiveItem(Item),
+    TakeItem(String),
+    Disposition { npc: String, amount: i32 },
+    Damage(Dice),
+    Heal(Dice),
+    Fight(String),
+    Xp { amount: i32, key: String },
+}
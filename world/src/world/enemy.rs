@@ -0,0 +1,91 @@
+use std::vec::Vec;
+use core::dice::Dice;
+use crate::world::effect::Effect;
+
+/// Something in a room the player can fight.
+///
+/// - hp: how many hit points the enemy has left, out of max_hp.
+/// - armor_class: what the players attack roll has to meet or beat to hit it.
+/// - attack_bonus: added to the enemies d20 when it attacks the player.
+/// - damage: the dice rolled when the enemy hits.
+/// - initiative: added to the enemies d20 when working out who goes first.
+/// - hostile: hostile enemies start a fight as soon as the player walks in.
+/// - xp: the experience the player gets for defeating it.
+///
+/// The effects are applied when the enemy is defeated, for example dropping loot or
+/// setting a flag so the story knows.
+///
+/// An NPC can be fought by giving the room an enemy with the same name. Defeating the
+/// enemy also removes the NPC from the room.
+#[derive(Clone, Debug)]
+pub struct Enemy {
+    pub name: String,
+    pub max_hp: i32,
+    pub hp: i32,
+    pub armor_class: i32,
+    pub attack_bonus: i32,
+    pub damage: Dice,
+    pub initiative: i32,
+    pub hostile: bool,
+    pub xp: i32,
+    pub effects: Vec<Effect>,
+}
+
+impl Enemy {
+
+    /// Create an enemy at full hit points.
+    pub fn new(name: String, hp: i32, armor_class: i32, attack_bonus: i32, damage: Dice) -> Self {
+        Enemy {
+            name: name,
+            max_hp: hp,
+            hp: hp,
+            armor_class: armor_class,
+            attack_bonus: attack_bonus,
+            damage: damage,
+            initiative: 0,
+            hostile: false,
+            xp: 0,
+            effects: Vec::new(),
+        }
+    }
+
+    /// Set what is added to the enemies initiative roll.
+    pub fn with_initiative(mut self, initiative: i32) -> Self {
+        self.initiative = initiative;
+
+        return self;
+    }
+
+    /// Make the enemy attack as soon as the player walks in, or not.
+    pub fn hostile(mut self, hostile: bool) -> Self {
+        self.hostile = hostile;
+
+        return self;
+    }
+
+    /// Set the experience the player gets for defeating the enemy.
+    pub fn with_xp(mut self, xp: i32) -> Self {
+        self.xp = xp;
+
+        return self;
+    }
+
+    /// Set the effects of defeating the enemy.
+    pub fn with_effects(mut self, effects: Vec<Effect>) -> Self {
+        self.effects = effects;
+
+        return self;
+    }
+
+    /// Is this the enemy with the name, ignoring case? Enemies in a room have different names,
+    /// so this picks out one enemy. Working out what the player meant by part of a name is done
+    /// by the game.
+    pub fn matches(&self, name: &str) -> bool {
+        return self.name.to_lowercase() == name.to_lowercase();
+    }
+
+    /// Has the enemy run out of hit points?
+    pub fn is_defeated(&self) -> bool {
+        return self.hp <= 0;
+    }
+}
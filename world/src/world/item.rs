@@ -0,0 +1,87 @@
+use std::vec::Vec;
+use serde::{Serialize, Deserialize};
+
+/// Something the player can pick up, carry around and drop.
+///
+/// The name is what the player sees, the aliases are other words the player can use
+/// to refer to it. For example a "Rusty Key" might have the alias "key".
+///
+/// Tags are free form and are for the adventure to use, for example: "key" or "letter".
+///
+/// An item with contents is a container, such as a chest, and the player can take things
+/// out of it and put things in it. An empty container has empty contents, items that
+/// aren't containers have none.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct Item {
+    pub name: String,
+    pub description: String,
+    pub aliases: Vec<String>,
+    pub weight: i32,
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub contents: Option<Vec<Item>>,
+}
+
+impl Item {
+
+    /// Create a new item.
+    pub fn new(name: String, description: String, aliases: Vec<String>, weight: i32, tags: Vec<String>) -> Self {
+        Item {
+            name: name,
+            description: description,
+            aliases: aliases,
+            weight: weight,
+            tags: tags,
+            contents: None,
+        }
+    }
+
+    /// Make the item a container holding the items.
+    pub fn with_contents(mut self, contents: Vec<Item>) -> Self {
+        self.contents = Some(contents);
+
+        return self;
+    }
+
+    /// Can things be put in the item?
+    pub fn is_container(&self) -> bool {
+        return self.contents.is_some();
+    }
+
+    /// Find an item inside the container by name or alias.
+    pub fn find_content(&self, name: &str) -> Option<&Item> {
+        let contents = self.contents.as_ref()?;
+
+        return find_item(contents, name).map(|index| &contents[index]);
+    }
+
+    /// Take an item out of the container.
+    pub fn take_content(&mut self, name: &str) -> Option<Item> {
+        let contents = self.contents.as_mut()?;
+
+        return find_item(contents, name).map(|index| contents.remove(index));
+    }
+
+    /// Does the name the player typed refer to this item?
+    ///
+    /// Matches the name or any of the aliases, ignoring case.
+    pub fn matches(&self, name: &str) -> bool {
+        let name = name.to_lowercase();
+
+        if self.name.to_lowercase() == name {
+            return true;
+        }
+
+        return self.aliases.iter().any(|alias| alias.to_lowercase() == name);
+    }
+
+    /// Does the item have the tag?
+    pub fn has_tag(&self, tag: &str) -> bool {
+        return self.tags.iter().any(|item_tag| item_tag == tag);
+    }
+}
+
+/// Find the position of the item the name refers to.
+pub fn find_item(items: &Vec<Item>, name: &str) -> Option<usize> {
+    return items.iter().position(|item| item.matches(name));
+}
@@ -0,0 +1,84 @@
+use std::vec::Vec;
+use core::stats::Stat;
+
+/// What the player has to reach to get to a level, and what they get for it.
+///
+/// - xp: the experience needed to reach the level.
+/// - max_hp: added to the characters max hp, they are healed by the same amount.
+/// - stats: added to the characters stats.
+/// - unlocks: class abilities that can't be used until the level is reached, for example:
+///   "pick lock" or "ability" for the ability used in a fight.
+///
+/// Every character starts at level 1 with 0 xp, so the table starts at level 2.
+#[derive(Clone, Debug)]
+pub struct Level {
+    pub level: i32,
+    pub xp: i32,
+    pub max_hp: i32,
+    pub stats: Vec<(Stat, i32)>,
+    pub unlocks: Vec<String>,
+}
+
+impl Level {
+
+    /// Create a level that only needs the xp.
+    pub fn new(level: i32, xp: i32) -> Self {
+        Level {
+            level: level,
+            xp: xp,
+            max_hp: 0,
+            stats: Vec::new(),
+            unlocks: Vec::new(),
+        }
+    }
+
+    /// Add to the characters max hp when they reach the level.
+    pub fn with_max_hp(mut self, max_hp: i32) -> Self {
+        self.max_hp = max_hp;
+
+        return self;
+    }
+
+    /// Add to the characters stats when they reach the level.
+    pub fn with_stats(mut self, stats: Vec<(Stat, i32)>) -> Self {
+        self.stats = stats;
+
+        return self;
+    }
+
+    /// Set the class abilities that unlock at the level.
+    pub fn with_unlocks(mut self, unlocks: Vec<String>) -> Self {
+        self.unlocks = unlocks;
+
+        return self;
+    }
+}
+
+/// How much experience the adventure gives for passing a DC check and for getting to the
+/// end of a conversation.
+///
+/// Fights give the xp of each enemy defeated, and quests give whatever their xp effect says.
+#[derive(Clone, Debug)]
+pub struct Experience {
+    pub check: i32,
+    pub conversation: i32,
+}
+
+impl Experience {
+
+    pub fn new(check: i32, conversation: i32) -> Self {
+        Experience {
+            check: check,
+            conversation: conversation,
+        }
+    }
+}
+
+impl Default for Experience {
+    fn default() -> Self {
+        return Experience::new(10, 10);
+    }
+}
+
+/// The class abilities a level can unlock.
+pub const UNLOCKABLE: [&str; 6] = ["cast", "steal", "pick lock", "force", "intimidate", "ability"];
@@ -0,0 +1,1050 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::vec::Vec;
+use std::collections::HashMap;
+use serde::Deserialize;
+use crate::World;
+use crate::world::actions::{Action, OnAction};
+use crate::world::conversation::{Conversation, Converse, Choices, SkillCheck};
+use crate::world::person::Person;
+use crate::world::enemy::Enemy;
+use crate::world::level::{Level, Experience, UNLOCKABLE};
+use crate::world::verb::Verb;
+use crate::world::item::Item;
+use crate::world::room::{Room, Exit, Door, GoBack, Direction, ConditionalText};
+use crate::world::condition::Condition;
+use crate::world::effect::Effect;
+use crate::world::state::Value;
+use core::dice::Dice;
+use core::stats::Stat;
+use core::stat_methods::StatMethod;
+
+/// The file formats an adventure can be written in.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Format {
+    Toml, Json, Ron
+}
+
+impl Format {
+
+    /// Work out the format from the files extension.
+    pub fn from_path(path: &Path) -> Option<Format> {
+        let extension = path.extension()?.to_str()?.to_lowercase();
+
+        match extension.as_str() {
+            "toml" => Some(Format::Toml),
+            "json" => Some(Format::Json),
+            "ron" => Some(Format::Ron),
+            _ => None,
+        }
+    }
+}
+
+/// Something went wrong loading an adventure.
+///
+/// Writers are the ones who see these, so we try to point at the file, the line
+/// and the room or conversation node that failed, when we know them.
+#[derive(Clone, Debug)]
+pub struct LoadError {
+    pub file: String,
+    pub line: Option<usize>,
+    pub node: Option<String>,
+    pub message: String,
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.file)?;
+
+        if let Some(line) = self.line {
+            write!(f, ":{}", line)?;
+        }
+
+        if let Some(node) = &self.node {
+            write!(f, " ({})", node)?;
+        }
+
+        write!(f, ": {}", self.message)
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+/// Load a world from an adventure file on disk.
+///
+/// The format is picked from the extension: .toml, .json or .ron.
+pub fn load_world(path: &Path) -> Result<World, LoadError> {
+    let file = path.display().to_string();
+
+    let format = match Format::from_path(path) {
+        Some(format) => format,
+        None => return Err(error(&file, None, None, "unknown adventure format, expected .toml, .json or .ron".to_string())),
+    };
+
+    let source = match fs::read_to_string(path) {
+        Ok(source) => source,
+        Err(e) => return Err(error(&file, None, None, format!("could not read file: {}", e))),
+    };
+
+    return load_world_from_str(&source, format, &file);
+}
+
+/// Load a world from the source of an adventure file.
+///
+/// `file` is only used to report errors.
+pub fn load_world_from_str(source: &str, format: Format, file: &str) -> Result<World, LoadError> {
+    let definition: WorldDef = match format {
+        Format::Toml => toml::from_str(source).map_err(|e| {
+            let line = e.span().map(|span| line_at(source, span.start));
+
+            error(file, line, None, e.message().to_string())
+        })?,
+        Format::Json => serde_json::from_str(source).map_err(|e| {
+            error(file, Some(e.line()), None, e.to_string())
+        })?,
+        Format::Ron => ron::from_str(source).map_err(|e| {
+            error(file, Some(e.span.start.line), None, e.code.to_string())
+        })?,
+    };
+
+    let builder = Builder {
+        file: file,
+        source: source,
+        rooms: definition.rooms.iter().map(|room| (room.id.as_str(), room)).collect(),
+        verbs: definition.commands.iter().map(|command| command.name.to_lowercase()).collect(),
+    };
+
+    if !builder.rooms.contains_key(definition.start.as_str()) {
+        return Err(error(file, None, Some("start".to_string()), format!("no room with the id: {}", definition.start)));
+    }
+
+    let mut rooms = Vec::new();
+
+    for room in &definition.rooms {
+        if rooms.iter().any(|built: &Room| built.id == room.id) {
+            return Err(builder.fail(&room.id, format!("room {}", room.id), "another room already uses this id.".to_string()));
+        }
+
+        rooms.push(builder.build_room(room)?);
+    }
+
+    let experience = match &definition.experience {
+        Some(experience) => Experience::new(experience.check, experience.conversation),
+        None => Experience::default(),
+    };
+
+    return Ok(World::new(definition.start.clone(), rooms)
+        .with_levels(builder.levels(&definition.levels)?)
+        .with_experience(experience)
+        .with_verbs(builder.verbs(&definition.commands)?)
+        .with_stat_methods(builder.stat_methods(&definition.stat_methods)?));
+}
+
+fn error(file: &str, line: Option<usize>, node: Option<String>, message: String) -> LoadError {
+    LoadError {
+        file: file.to_string(),
+        line: line,
+        node: node,
+        message: message,
+    }
+}
+
+/// The line number (starting at 1) of a byte offset in the source.
+fn line_at(source: &str, offset: usize) -> usize {
+    return source[..offset.min(source.len())].matches('\n').count() + 1;
+}
+
+/// The first line that defines the given id or name.
+///
+/// Every format quotes its values, so we look for an id or name key set to the quoted value:
+/// `id = "river"` in toml, `"id": "river"` in json and `id: "river"` in ron.
+fn line_of_id(source: &str, id: &str) -> Option<usize> {
+    let quoted = format!("\"{}\"", id);
+
+    for (number, line) in source.lines().enumerate() {
+        let line: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+
+        for key in ["id", "name"] {
+            let keys = [format!("{}={}", key, quoted), format!("\"{}\":{}", key, quoted), format!("{}:{}", key, quoted)];
+
+            for pattern in &keys {
+                let found = line.match_indices(pattern.as_str()).any(|(index, _)| {
+                    !line[..index].ends_with(|c: char| c.is_alphanumeric() || c == '_')
+                });
+
+                if found {
+                    return Some(number + 1);
+                }
+            }
+        }
+    }
+
+    return None;
+}
+
+/// Find the line a level is defined on, by looking for its level number.
+fn line_of_level(source: &str, level: i32) -> Option<usize> {
+    for (number, line) in source.lines().enumerate() {
+        let line: String = line.chars().filter(|c| !c.is_whitespace()).collect();
+
+        if line.contains(&format!("level={}", level)) || line.contains(&format!("\"level\":{}", level)) || line.contains(&format!("level:{}", level)) {
+            return Some(number + 1);
+        }
+    }
+
+    return None;
+}
+
+/// Find the line a top level key is on.
+fn line_of_key(source: &str, key: &str) -> Option<usize> {
+    for (number, line) in source.lines().enumerate() {
+        let line = line.trim_start();
+
+        if line.starts_with(key) || line.starts_with(&format!("\"{}\"", key)) {
+            return Some(number + 1);
+        }
+    }
+
+    return None;
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WorldDef {
+    start: String,
+    rooms: Vec<RoomDef>,
+    experience: Option<ExperienceDef>,
+    #[serde(default)]
+    levels: Vec<LevelDef>,
+    #[serde(default)]
+    commands: Vec<VerbDef>,
+    stat_methods: Option<Vec<String>>,
+}
+
+/// A command the adventure adds, for example:
+/// `{ name = "pray", aliases = ["kneel"], help = "Pray to the old gods.", on_action = "Nothing answers." }`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct VerbDef {
+    name: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    usage: Option<String>,
+    help: String,
+    stat: Option<String>,
+    on_action: Option<String>,
+    dc_check: Option<i32>,
+    #[serde(default)]
+    conditions: Vec<ConditionDef>,
+    failure: Option<String>,
+    #[serde(default)]
+    effects: Vec<EffectDef>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExperienceDef {
+    #[serde(default)]
+    check: i32,
+    #[serde(default)]
+    conversation: i32,
+}
+
+/// A level in the level table, for example:
+/// `{ level = 2, xp = 100, max_hp = 4, stats = { str = 1 }, unlocks = ["ability"] }`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LevelDef {
+    level: i32,
+    xp: i32,
+    #[serde(default)]
+    max_hp: i32,
+    #[serde(default)]
+    stats: HashMap<String, i32>,
+    #[serde(default)]
+    unlocks: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RoomDef {
+    id: String,
+    name: String,
+    description: String,
+    #[serde(default)]
+    actions: Vec<ActionDef>,
+    #[serde(default)]
+    exits: Vec<ExitDef>,
+    #[serde(default)]
+    go_back: GoBackDef,
+    #[serde(default)]
+    npcs: Vec<PersonDef>,
+    #[serde(default)]
+    items: Vec<ItemDef>,
+    #[serde(default)]
+    descriptions: Vec<TextDef>,
+    #[serde(default)]
+    fragments: Vec<TextDef>,
+    #[serde(default)]
+    dark: bool,
+    #[serde(default)]
+    enemies: Vec<EnemyDef>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EnemyDef {
+    name: String,
+    hp: i32,
+    armor_class: i32,
+    #[serde(default)]
+    attack_bonus: i32,
+    damage: String,
+    #[serde(default)]
+    initiative: i32,
+    #[serde(default)]
+    hostile: bool,
+    #[serde(default = "default_enemy_xp")]
+    xp: i32,
+    #[serde(default)]
+    effects: Vec<EffectDef>,
+}
+
+fn default_enemy_xp() -> i32 {
+    return 10;
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TextDef {
+    text: String,
+    #[serde(default)]
+    conditions: Vec<ConditionDef>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ItemDef {
+    name: String,
+    description: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    weight: i32,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    container: bool,
+    contents: Option<Vec<ItemDef>>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ActionDef {
+    action: String,
+    on_action: Option<String>,
+    dc_check: Option<i32>,
+    #[serde(default)]
+    conditions: Vec<ConditionDef>,
+    failure: Option<String>,
+    #[serde(default)]
+    effects: Vec<EffectDef>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ExitDef {
+    direction: Option<String>,
+    room: Option<String>,
+    name: Option<String>,
+    door: Option<DoorDef>,
+    #[serde(default)]
+    conditions: Vec<ConditionDef>,
+    #[serde(default)]
+    hidden: bool,
+    failure: Option<String>,
+    #[serde(default)]
+    effects: Vec<EffectDef>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DoorDef {
+    name: String,
+    #[serde(default)]
+    closed: bool,
+    #[serde(default)]
+    locked: bool,
+    key: Option<String>,
+    force_dc: Option<i32>,
+    pick_dc: Option<i32>,
+}
+
+/// A condition is written as a table with one kind of check in it, for example:
+/// `{ item = "Rusty Key" }`, `{ flag = "met_the_poet" }`, `{ not_flag = "met_the_poet" }`,
+/// `{ state = "mood", equals = "angry" }`, `{ state = "coins", at_least = 3 }`,
+/// `{ check = "dex", dc = 12 }`, `{ stat = "chr", at_least = 14 }`, `{ class = "Wizard" }`,
+/// `{ can = "steal" }`, `{ trait = "darkvision" }` or `{ disposition = "Mysterious Old Man", at_least = 2 }`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConditionDef {
+    item: Option<String>,
+    flag: Option<String>,
+    not_flag: Option<String>,
+    state: Option<String>,
+    equals: Option<ValueDef>,
+    at_least: Option<i32>,
+    check: Option<String>,
+    dc: Option<i32>,
+    stat: Option<String>,
+    class: Option<String>,
+    can: Option<String>,
+    #[serde(rename = "trait")]
+    race_trait: Option<String>,
+    disposition: Option<String>,
+}
+
+/// An effect is written as a table with one kind of change in it, for example:
+/// `{ set = "met_the_poet" }`, `{ set = "mood", value = "angry" }`, `{ add = "coins", amount = 2 }`,
+/// `{ give = { name = "Letter", description = "..." } }`, `{ take = "Letter" }`,
+/// `{ disposition = "Mysterious Old Man", amount = 1 }`, `{ damage = "1d4" }`, `{ heal = "2d4+2" }`,
+/// `{ fight = "Goblin" }` or `{ xp = 50 }`.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct EffectDef {
+    set: Option<String>,
+    value: Option<ValueDef>,
+    add: Option<String>,
+    amount: Option<i32>,
+    give: Option<ItemDef>,
+    take: Option<String>,
+    disposition: Option<String>,
+    damage: Option<String>,
+    heal: Option<String>,
+    fight: Option<String>,
+    xp: Option<i32>,
+}
+
+#[derive(Deserialize, Clone)]
+#[serde(untagged)]
+enum ValueDef {
+    Bool(bool),
+    Int(i32),
+    Str(String),
+}
+
+impl ValueDef {
+    fn value(&self) -> Value {
+        match self.clone() {
+            ValueDef::Bool(value) => Value::Bool(value),
+            ValueDef::Int(value) => Value::Int(value),
+            ValueDef::Str(value) => Value::Str(value),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GoBackDef {
+    can_go_back: bool,
+    reason: Option<String>,
+}
+
+impl Default for GoBackDef {
+    fn default() -> Self {
+        GoBackDef {
+            can_go_back: true,
+            reason: None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PersonDef {
+    name: String,
+    conversation: String,
+    nodes: Vec<NodeDef>,
+    #[serde(default)]
+    disposition: i32,
+    #[serde(default)]
+    actions: Vec<ActionDef>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NodeDef {
+    id: String,
+    line: String,
+    #[serde(default)]
+    choices: Vec<ChoiceDef>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ChoiceDef {
+    choice: String,
+    next: String,
+    #[serde(default)]
+    conditions: Vec<ConditionDef>,
+    #[serde(default)]
+    effects: Vec<EffectDef>,
+    check: Option<CheckDef>,
+}
+
+/// A skill check on a choice, for example: `{ stat = "chr", dc = 12, failure = "not_convinced" }`.
+/// The choice's next node is where the conversation goes on a success.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CheckDef {
+    stat: String,
+    dc: i32,
+    failure: String,
+}
+
+/// Turns the flat, id based definitions into rooms and conversation graphs.
+struct Builder<'a> {
+    file: &'a str,
+    source: &'a str,
+    rooms: HashMap<&'a str, &'a RoomDef>,
+    verbs: Vec<String>,
+}
+
+impl<'a> Builder<'a> {
+
+    fn fail(&self, id: &str, node: String, message: String) -> LoadError {
+        error(self.file, line_of_id(self.source, id), Some(node), message)
+    }
+
+    /// Build a room, checking that each of its exits leads to a room that exists.
+    fn build_room(&self, definition: &RoomDef) -> Result<Room, LoadError> {
+        let id = definition.id.as_str();
+        let node = format!("room {}", id);
+
+        let mut actions = HashMap::new();
+
+        for action in &definition.actions {
+            let on_action = match &action.on_action {
+                Some(on_action) => Some(OnAction::new(on_action.clone(), action.dc_check)
+                    .with_conditions(self.conditions(id, &node, &action.conditions)?, action.failure.clone())
+                    .with_effects(self.effects(id, &format!("{}, action {}", node, action.action), &action.effects)?)
+                    .with_key(format!("{}, action {}", node, action.action).to_lowercase())),
+                None => None,
+            };
+
+            actions.insert(self.action(id, &node, &action.action)?, on_action);
+        }
+
+        let mut exits = Vec::new();
+
+        for exit in &definition.exits {
+            let direction = match (&exit.direction, &exit.name) {
+                (Some(direction), _) => self.direction(id, &node, direction)?,
+                (None, Some(_)) => Direction::NONE,
+                (None, None) => return Err(self.fail(id, node, "an exit needs a direction or a name".to_string())),
+            };
+
+            match (&exit.room, &exit.name) {
+                (Some(room), _) if !self.rooms.contains_key(room.as_str()) => {
+                    return Err(self.fail(id, node, format!("no room with the id: {}", room)));
+                },
+                (None, Some(name)) => {
+                    return Err(self.fail(id, node, format!("the exit {} needs a room to lead to", name)));
+                },
+                (None, None) if direction != Direction::NONE => {
+                    return Err(self.fail(id, node, format!("the exit to the {} needs a room to lead to", direction.name())));
+                },
+                _ => {},
+            }
+
+            let mut built = Exit::new(direction, exit.room.clone())
+                .with_name(exit.name.clone())
+                .with_conditions(match exit.hidden {
+                    true => self.shown_conditions(id, &node, &exit.conditions, "a hidden exit")?,
+                    false => self.conditions(id, &node, &exit.conditions)?,
+                }, exit.failure.clone())
+                .with_effects(self.effects(id, &format!("{}, exit {}", node, exits.len() + 1), &exit.effects)?)
+                .hidden(exit.hidden);
+
+            if let Some(door) = &exit.door {
+                built = built.with_door(Door::new(door.name.clone(), door.closed || door.locked, door.locked, door.key.clone(), door.force_dc)
+                    .with_pick_dc(door.pick_dc));
+            }
+
+            exits.push(built);
+        }
+
+        let mut npcs = Vec::new();
+
+        for person in &definition.npcs {
+            npcs.push(self.build_person(&node, person)?);
+        }
+
+        let mut enemies = Vec::new();
+
+        for enemy in &definition.enemies {
+            let node = format!("room {}, enemy {}", id, enemy.name);
+
+            let damage = match Dice::parse(&enemy.damage) {
+                Ok(damage) => damage,
+                Err(e) => return Err(self.fail(&enemy.name, node, format!("bad damage dice, {}", e))),
+            };
+
+            if enemy.hp <= 0 {
+                return Err(self.fail(&enemy.name, node, "an enemy needs at least 1 hp.".to_string()));
+            }
+
+            if enemies.iter().any(|built: &Enemy| built.matches(&enemy.name)) {
+                return Err(self.fail(&enemy.name, node, "another enemy in the room already has this name.".to_string()));
+            }
+
+            enemies.push(Enemy::new(enemy.name.clone(), enemy.hp, enemy.armor_class, enemy.attack_bonus, damage)
+                .with_initiative(enemy.initiative)
+                .hostile(enemy.hostile)
+                .with_xp(enemy.xp)
+                .with_effects(self.effects(&enemy.name, &node, &enemy.effects)?));
+        }
+
+        let mut descriptions = Vec::new();
+
+        for description in &definition.descriptions {
+            descriptions.push(ConditionalText::new(self.shown_conditions(id, &node, &description.conditions, "a description")?, description.text.clone()));
+        }
+
+        let mut fragments = Vec::new();
+
+        for fragment in &definition.fragments {
+            fragments.push(ConditionalText::new(self.shown_conditions(id, &node, &fragment.conditions, "a fragment")?, fragment.text.clone()));
+        }
+
+        return Ok(Room::new(
+            definition.id.clone(),
+            definition.name.clone(),
+            definition.description.clone(),
+            actions,
+            exits,
+            GoBack::new(definition.go_back.can_go_back, definition.go_back.reason.clone()),
+            if npcs.is_empty() { None } else { Some(npcs) },
+        ).with_items(definition.items.iter().map(|item| self.build_item(item)).collect())
+         .with_descriptions(descriptions)
+         .with_fragments(fragments)
+         .dark(definition.dark)
+         .with_enemies(enemies));
+    }
+
+    /// Get the ways the player can make their stats, every method is allowed when the adventure
+    /// doesn't list any.
+    fn stat_methods(&self, names: &Option<Vec<String>>) -> Result<Vec<StatMethod>, LoadError> {
+        let names = match names {
+            Some(names) => names,
+            None => return Ok(StatMethod::all()),
+        };
+
+        let fail = |message: String| error(self.file, line_of_key(self.source, "stat_methods"), Some("stat_methods".to_string()), message);
+
+        if names.is_empty() {
+            return Err(fail("an adventure needs at least one way to make stats.".to_string()));
+        }
+
+        let mut methods: Vec<StatMethod> = Vec::new();
+
+        for name in names {
+            let method = match StatMethod::from_name(name) {
+                Some(method) => method,
+                None => return Err(fail(format!("no way to make stats called: {}, expected roll, 4d6, array, point_buy, point_buy:<budget> or manual.", name))),
+            };
+
+            if methods.iter().any(|listed| listed.name() == method.name()) {
+                return Err(fail(format!("{} is listed more than once.", method.name())));
+            }
+
+            methods.push(method);
+        }
+
+        return Ok(methods);
+    }
+
+    /// Build the level table, checking the stats and unlocks exist and that each level
+    /// follows on from the last one and needs more xp.
+    fn levels(&self, levels: &Vec<LevelDef>) -> Result<Vec<Level>, LoadError> {
+        let mut built: Vec<Level> = Vec::new();
+
+        for level in levels {
+            let node = format!("level {}", level.level);
+            let fail = |message: String| error(self.file, line_of_level(self.source, level.level), Some(node.clone()), message);
+
+            let (previous, previous_xp) = match built.last() {
+                Some(previous) => (previous.level, previous.xp),
+                None => (1, 0),
+            };
+
+            if level.level != previous + 1 {
+                return Err(fail(format!("expected level {} next, levels start at 2 and go up by one.", previous + 1)));
+            }
+
+            if level.xp <= previous_xp {
+                return Err(fail(format!("a level needs more xp than the level before it, which needs {}.", previous_xp)));
+            }
+
+            let mut stats = Vec::new();
+
+            for (name, amount) in &level.stats {
+                match Stat::from_name(name) {
+                    Some(stat) => stats.push((stat, *amount)),
+                    None => return Err(fail(format!("unknown stat: {}", name))),
+                }
+            }
+
+            stats.sort_by_key(|(stat, _)| Stat::all().iter().position(|other| other == stat));
+
+            for unlock in &level.unlocks {
+                if !UNLOCKABLE.contains(&unlock.to_lowercase().as_str()) {
+                    return Err(fail(format!("unknown class ability: {}, expected one of: {}.", unlock, UNLOCKABLE.join(", "))));
+                }
+            }
+
+            built.push(Level::new(level.level, level.xp)
+                .with_max_hp(level.max_hp)
+                .with_stats(stats)
+                .with_unlocks(level.unlocks.iter().map(|unlock| unlock.to_lowercase()).collect()));
+        }
+
+        return Ok(built);
+    }
+
+    /// Build the item. An item with contents, or marked as a container, can hold other items.
+    fn build_item(&self, item: &ItemDef) -> Item {
+        let built = Item::new(item.name.clone(), item.description.clone(), item.aliases.clone(), item.weight, item.tags.clone());
+
+        match &item.contents {
+            Some(contents) => built.with_contents(contents.iter().map(|content| self.build_item(content)).collect()),
+            None if item.container => built.with_contents(Vec::new()),
+            None => built,
+        }
+    }
+
+    /// Build the person in the room and their conversation, checking that every choice leads
+    /// to a node that exists. Choices may lead back to earlier nodes.
+    fn build_person(&self, room: &str, person: &PersonDef) -> Result<Person, LoadError> {
+        let key = format!("{}, person {}", room, person.name).to_lowercase();
+
+        let mut ids: Vec<&str> = Vec::new();
+
+        for definition in &person.nodes {
+            if ids.contains(&definition.id.as_str()) {
+                return Err(self.fail(&definition.id, format!("{}, node {}", person.name, definition.id), format!("duplicate conversation node id: {}", definition.id)));
+            }
+
+            ids.push(definition.id.as_str());
+        }
+
+        if !ids.contains(&person.conversation.as_str()) {
+            return Err(self.fail(&person.name, person.name.clone(), format!("no conversation node with the id: {}", person.conversation)));
+        }
+
+        let mut nodes = Vec::new();
+
+        for definition in &person.nodes {
+            let id = definition.id.as_str();
+            let node = format!("{}, node {}", person.name, id);
+
+            let mut choices = Vec::new();
+
+            for (index, choice) in definition.choices.iter().enumerate() {
+                if !ids.contains(&choice.next.as_str()) {
+                    return Err(self.fail(id, node, format!("no conversation node with the id: {}", choice.next)));
+                }
+
+                let mut built = Choices::new(choice.choice.clone(), choice.next.clone())
+                    .with_conditions(self.shown_conditions(id, &node, &choice.conditions, "a choice")?)
+                    .with_effects(self.effects(id, &format!("{}, node {}, choice {}", key, id, index + 1), &choice.effects)?)
+                    .with_key(format!("{}, node {}, choice {}", key, id, index + 1));
+
+                if let Some(check) = &choice.check {
+                    let stat = match Stat::from_name(&check.stat) {
+                        Some(stat) => stat,
+                        None => return Err(self.fail(id, node, format!("unknown stat: {}", check.stat))),
+                    };
+
+                    if !ids.contains(&check.failure.as_str()) {
+                        return Err(self.fail(id, node, format!("no conversation node with the id: {}", check.failure)));
+                    }
+
+                    built = built.with_check(SkillCheck::new(stat, check.dc, check.failure.clone()));
+                }
+
+                choices.push(built);
+            }
+
+            nodes.push(Converse::new(definition.id.clone(), definition.line.clone(), if choices.is_empty() { None } else { Some(choices) }));
+        }
+
+        let conversation = Conversation::new(person.conversation.clone(), nodes);
+
+        let mut actions = HashMap::new();
+
+        for action in &person.actions {
+            let node = format!("{}, action {}", person.name, action.action);
+
+            let on_action = match &action.on_action {
+                Some(on_action) => OnAction::new(on_action.clone(), action.dc_check)
+                    .with_conditions(self.conditions(&person.name, &node, &action.conditions)?, action.failure.clone())
+                    .with_effects(self.effects(&person.name, &format!("{}, action {}", key, action.action), &action.effects)?)
+                    .with_key(format!("{}, action {}", key, action.action).to_lowercase()),
+                None => return Err(self.fail(&person.name, node, "an action on a person needs on_action.".to_string())),
+            };
+
+            actions.insert(self.action(&person.name, &node, &action.action)?, on_action);
+        }
+
+        let actions = if actions.is_empty() { None } else { Some(actions) };
+
+        return Ok(Person::new(person.name.clone(), actions, conversation).with_disposition(person.disposition));
+    }
+
+    fn conditions(&self, id: &str, node: &str, conditions: &Vec<ConditionDef>) -> Result<Vec<Condition>, LoadError> {
+        let mut built = Vec::new();
+
+        for condition in conditions {
+            built.push(self.condition(id, node, condition)?);
+        }
+
+        return Ok(built);
+    }
+
+    /// Conditions that are checked every time something is shown to the player, such as a
+    /// description or the choices in a conversation. A check rolls dice, so it can't be used here,
+    /// other wise every look would roll and the result would change each time.
+    fn shown_conditions(&self, id: &str, node: &str, conditions: &Vec<ConditionDef>, what: &str) -> Result<Vec<Condition>, LoadError> {
+        let built = self.conditions(id, node, conditions)?;
+
+        if built.iter().any(|condition| matches!(condition, Condition::StatCheck { .. })) {
+            return Err(self.fail(id, node.to_string(), format!("{} can't have a check condition, it would roll every time it is shown. Use stat instead.", what)));
+        }
+
+        return Ok(built);
+    }
+
+    fn condition(&self, id: &str, node: &str, condition: &ConditionDef) -> Result<Condition, LoadError> {
+        let kinds = [
+            condition.item.is_some(),
+            condition.flag.is_some(),
+            condition.not_flag.is_some(),
+            condition.state.is_some(),
+            condition.check.is_some(),
+            condition.stat.is_some(),
+            condition.class.is_some(),
+            condition.can.is_some(),
+            condition.race_trait.is_some(),
+            condition.disposition.is_some(),
+        ];
+
+        if kinds.iter().filter(|kind| **kind).count() != 1 {
+            return Err(self.fail(id, node.to_string(), "a condition needs exactly one of: item, flag, not_flag, state, check, stat, class, can, trait or disposition.".to_string()));
+        }
+
+        if let Some(class) = &condition.class {
+            return Ok(Condition::Class(class.clone()));
+        }
+
+        if let Some(can) = &condition.can {
+            return match can.to_lowercase().as_str() {
+                "cast" => Ok(Condition::CanCast),
+                "steal" => Ok(Condition::CanSteal),
+                "force" => Ok(Condition::CanForce),
+                _ => Err(self.fail(id, node.to_string(), format!("unknown class ability: {}, expected cast, steal or force.", can))),
+            };
+        }
+
+        if let Some(race_trait) = &condition.race_trait {
+            return Ok(Condition::Trait(race_trait.clone()));
+        }
+
+        if let Some(npc) = &condition.disposition {
+            return match condition.at_least {
+                Some(value) => Ok(Condition::Disposition { npc: npc.clone(), value: value }),
+                None => Err(self.fail(id, node.to_string(), format!("the disposition condition on {} needs at_least.", npc))),
+            };
+        }
+
+        if let Some(name) = &condition.stat {
+            let stat = match Stat::from_name(name) {
+                Some(stat) => stat,
+                None => return Err(self.fail(id, node.to_string(), format!("unknown stat: {}", name))),
+            };
+
+            return match condition.at_least {
+                Some(value) => Ok(Condition::MinStat { stat: stat, value: value }),
+                None => Err(self.fail(id, node.to_string(), format!("the {} condition needs at_least.", name))),
+            };
+        }
+
+        if let Some(item) = &condition.item {
+            return Ok(Condition::HasItem(item.clone()));
+        }
+
+        if let Some(flag) = &condition.flag {
+            return Ok(Condition::Flag(flag.clone()));
+        }
+
+        if let Some(flag) = &condition.not_flag {
+            return Ok(Condition::NotFlag(flag.clone()));
+        }
+
+        if let Some(key) = &condition.state {
+            return match (&condition.equals, condition.at_least) {
+                (Some(value), None) => Ok(Condition::Equals { key: key.clone(), value: value.value() }),
+                (None, Some(value)) => Ok(Condition::AtLeast { key: key.clone(), value: value }),
+                _ => Err(self.fail(id, node.to_string(), format!("the state condition on {} needs one of: equals or at_least.", key))),
+            };
+        }
+
+        let check = condition.check.clone().unwrap();
+
+        let stat = match Stat::from_name(&check) {
+            Some(stat) => stat,
+            None => return Err(self.fail(id, node.to_string(), format!("unknown stat: {}", check))),
+        };
+
+        match condition.dc {
+            Some(dc) => Ok(Condition::StatCheck { stat: stat, dc: dc }),
+            None => Err(self.fail(id, node.to_string(), format!("the {} check needs a dc.", check))),
+        }
+    }
+
+    fn effects(&self, id: &str, node: &str, effects: &Vec<EffectDef>) -> Result<Vec<Effect>, LoadError> {
+        let mut built = Vec::new();
+
+        for effect in effects {
+            built.push(self.effect(id, node, effect, built.len())?);
+        }
+
+        return Ok(built);
+    }
+
+    /// Build the effect at the index in its list. Xp is keyed by where it is, so it is only given once.
+    fn effect(&self, id: &str, node: &str, effect: &EffectDef, index: usize) -> Result<Effect, LoadError> {
+        let kinds = [
+            effect.set.is_some(),
+            effect.add.is_some(),
+            effect.give.is_some(),
+            effect.take.is_some(),
+            effect.disposition.is_some(),
+            effect.damage.is_some(),
+            effect.heal.is_some(),
+            effect.fight.is_some(),
+            effect.xp.is_some(),
+        ];
+
+        if kinds.iter().filter(|kind| **kind).count() != 1 {
+            return Err(self.fail(id, node.to_string(), "an effect needs exactly one of: set, add, give, take, disposition, damage, heal, fight or xp.".to_string()));
+        }
+
+        if let Some(xp) = effect.xp {
+            return Ok(Effect::Xp { amount: xp, key: format!("{}, effect {}", node, index + 1).to_lowercase() });
+        }
+
+        if let Some(enemy) = &effect.fight {
+            return Ok(Effect::Fight(enemy.clone()));
+        }
+
+        if let Some(expression) = &effect.damage {
+            return Dice::parse(expression).map(Effect::Damage).map_err(|e| self.fail(id, node.to_string(), format!("bad damage dice, {}", e)));
+        }
+
+        if let Some(expression) = &effect.heal {
+            return Dice::parse(expression).map(Effect::Heal).map_err(|e| self.fail(id, node.to_string(), format!("bad heal dice, {}", e)));
+        }
+
+        if let Some(item) = &effect.give {
+            return Ok(Effect::GiveItem(self.build_item(item)));
+        }
+
+        if let Some(item) = &effect.take {
+            return Ok(Effect::TakeItem(item.clone()));
+        }
+
+        if let Some(npc) = &effect.disposition {
+            return match effect.amount {
+                Some(amount) => Ok(Effect::Disposition { npc: npc.clone(), amount: amount }),
+                None => Err(self.fail(id, node.to_string(), format!("the disposition effect on {} needs an amount.", npc))),
+            };
+        }
+
+        match (&effect.set, &effect.add) {
+            (Some(key), None) => {
+                let value = match &effect.value {
+                    Some(value) => value.value(),
+                    None => Value::Bool(true),
+                };
+
+                Ok(Effect::Set { key: key.clone(), value: value })
+            },
+            _ => Ok(Effect::Add { key: effect.add.clone().unwrap(), amount: effect.amount.unwrap_or(1) }),
+        }
+    }
+
+    /// Get the action, which can also be one of the commands the adventure adds.
+    fn action(&self, id: &str, node: &str, action: &str) -> Result<Action, LoadError> {
+        if let Some(action) = Action::from_name(action) {
+            return Ok(action);
+        }
+
+        if self.verbs.contains(&action.to_lowercase()) {
+            return Ok(Action::Custom(action.to_lowercase()));
+        }
+
+        return Err(self.fail(id, node.to_string(), format!("unknown action: {}", action)));
+    }
+
+    /// Build the commands the adventure adds. Each is a single word the player types first.
+    fn verbs(&self, verbs: &Vec<VerbDef>) -> Result<Vec<Verb>, LoadError> {
+        let mut built = Vec::new();
+
+        for verb in verbs {
+            let node = format!("command {}", verb.name);
+
+            for word in std::iter::once(&verb.name).chain(verb.aliases.iter()) {
+                if word.split_whitespace().count() != 1 {
+                    return Err(self.fail(&verb.name, node, format!("a command must be a single word: {}", word)));
+                }
+            }
+
+            let stat = match &verb.stat {
+                Some(name) => match Stat::from_name(name) {
+                    Some(stat) => stat,
+                    None => return Err(self.fail(&verb.name, node, format!("unknown stat: {}", name))),
+                },
+                None => Stat::Int,
+            };
+
+            let on_action = match &verb.on_action {
+                Some(on_action) => Some(OnAction::new(on_action.clone(), verb.dc_check)
+                    .with_conditions(self.conditions(&verb.name, &node, &verb.conditions)?, verb.failure.clone())
+                    .with_effects(self.effects(&verb.name, &node, &verb.effects)?)
+                    .with_key(node.to_lowercase())),
+                None => None,
+            };
+
+            built.push(Verb::new(verb.name.to_lowercase(), verb.help.clone())
+                .with_aliases(verb.aliases.iter().map(|alias| alias.to_lowercase()).collect())
+                .with_usage(verb.usage.clone().unwrap_or(verb.name.to_lowercase()))
+                .with_stat(stat)
+                .with_on_action(on_action));
+        }
+
+        return Ok(built);
+    }
+
+    fn direction(&self, id: &str, node: &str, direction: &str) -> Result<Direction, LoadError> {
+        match direction.to_lowercase().as_str() {
+            "none" => Ok(Direction::NONE),
+            "back" => Err(self.fail(id, node.to_string(), "an exit can't go back, the game always lets the player go back".to_string())),
+            word => match Direction::from_word(word) {
+                Some(direction) => Ok(direction),
+                None => Err(self.fail(id, node.to_string(), format!("unknown direction: {}", direction))),
+            },
+        }
+    }
+}
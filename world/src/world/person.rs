@@ -1,22 +1,47 @@
 use std::collections::HashMap;
 
 use crate::world::actions::{Action, OnAction};
-use crate::world::conversation::{Converse};
+use crate::world::conversation::Conversation;
 
 
+/// Someone the player can meet.
+///
+/// Disposition is how the person feels about the player. It starts at 0, goes up when the
+/// player is kind to them and down when they are not. Conversations can change it and
+/// choices can depend on it.
+///
+/// Actions are how the person responds when the player does something to them, such as
+/// stealing from them or casting a spell on them.
 #[derive(Clone, Debug)]
 pub struct Person {
     pub name: String,
     pub actions: Option<HashMap<Action, OnAction>>,
-    pub conversation: Converse,
+    pub conversation: Conversation,
+    pub disposition: i32,
 }
 
 impl Person {
-    pub fn new(name: String, actions: Option<HashMap<Action, OnAction>>, conversation: Converse) -> Self {
+    pub fn new(name: String, actions: Option<HashMap<Action, OnAction>>, conversation: Conversation) -> Self {
         Person {
             name: name,
             actions: actions,
             conversation: conversation,
+            disposition: 0,
         }
     }
+
+    /// How the person responds to the action, if they do.
+    pub fn action(&self, action: &Action) -> Option<&OnAction> {
+        return match &self.actions {
+            Some(actions) => actions.get(action),
+            None => None,
+        };
+    }
+
+    /// Set how the person feels about the player to start with.
+    pub fn with_disposition(mut self, disposition: i32) -> Self {
+        self.disposition = disposition;
+
+        return self;
+    }
 }
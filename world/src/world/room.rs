@@ -1,13 +1,74 @@
-use rand::Rng;
 use std::vec::Vec;
 use std::collections::HashMap;
 use crate::actions::{Action, OnAction};
 use crate::person::Person;
+use crate::enemy::Enemy;
+use crate::item::{Item, find_item};
+use crate::condition::Condition;
+use crate::effect::Effect;
+use crate::context::Context;
+use crate::description::Description;
 
 /// Directions the player can move in.
+///
+/// NONE is for exits that aren't in a direction, such as a named exit: enter house, or a room
+/// with no way out.
 #[derive(Clone, PartialEq, Debug)]
 pub enum Direction {
-    N, S, W, E, NONE, BACK
+    N, S, W, E, NE, NW, SE, SW, UP, DOWN, IN, OUT, NONE, BACK
+}
+
+impl Direction {
+
+    /// Every direction the player can go in.
+    pub fn all() -> Vec<Direction> {
+        return vec![
+            Direction::N, Direction::S, Direction::E, Direction::W,
+            Direction::NE, Direction::NW, Direction::SE, Direction::SW,
+            Direction::UP, Direction::DOWN, Direction::IN, Direction::OUT,
+            Direction::BACK,
+        ];
+    }
+
+    /// Get a direction from what the player typed, for example: n, north, ne or north east.
+    pub fn from_word(word: &str) -> Option<Direction> {
+        match word {
+            "n" | "north" => Some(Direction::N),
+            "s" | "south" => Some(Direction::S),
+            "e" | "east" => Some(Direction::E),
+            "w" | "west" => Some(Direction::W),
+            "ne" | "northeast" | "north east" | "north-east" => Some(Direction::NE),
+            "nw" | "northwest" | "north west" | "north-west" => Some(Direction::NW),
+            "se" | "southeast" | "south east" | "south-east" => Some(Direction::SE),
+            "sw" | "southwest" | "south west" | "south-west" => Some(Direction::SW),
+            "u" | "up" => Some(Direction::UP),
+            "d" | "down" => Some(Direction::DOWN),
+            "in" | "inside" => Some(Direction::IN),
+            "out" | "outside" => Some(Direction::OUT),
+            "back" => Some(Direction::BACK),
+            _ => None,
+        }
+    }
+
+    /// The name of the direction as shown to the player.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Direction::N => "north",
+            Direction::S => "south",
+            Direction::E => "east",
+            Direction::W => "west",
+            Direction::NE => "northeast",
+            Direction::NW => "northwest",
+            Direction::SE => "southeast",
+            Direction::SW => "southwest",
+            Direction::UP => "up",
+            Direction::DOWN => "down",
+            Direction::IN => "in",
+            Direction::OUT => "out",
+            Direction::BACK => "back",
+            Direction::NONE => "nowhere",
+        }
+    }
 }
 
 /// Room deffinition.
@@ -16,26 +77,24 @@ pub enum Direction {
 /// what can I do? (Action)
 /// Where can I go? (Exit)
 ///
-/// Rooms are built start to finish. That is its a nested tree where you progress top
-/// down through the various rooms. Rooms can branch off with Exits.
+/// Every room has an id that is unique in its world. Exits point to the id of the room
+/// they lead to, so rooms form a graph rather then a tree:
 ///
-/// To visualize this imagine:
+/// ```text
 ///
-/// ```
-///
-/// room 1
-///   |
-///  Exits -> Room 2 or Room 3 or ...
+/// room 1 <------------
+///   |                 |
+///  Exits -> room 2 or room 3
 ///             |        |
-///           Exits .. Exits ..
+///           Exits .. Exits -> room 1
 ///
 /// ```
 ///
-/// All rooms must start from the rop down and based on the direction the plater takes
-/// we then find that room in the vector of exits and load it.
+/// Based on the direction the player takes we find the exit and then look up the
+/// room it leads to in the world.
 ///
-/// Rooms do not have to link back to the previous room, because the world contains the current room
-/// and the previous room.
+/// Rooms do not have to link back to the previous room, because the game keeps track of
+/// the room the player came from.
 ///
 /// When a player does an action on a room we want to process that action, to do this we take in the action that
 /// the user types, for example: "look" or "explore", from there we loop over the rooms actions looking for
@@ -46,17 +105,55 @@ pub enum Direction {
 /// a room that allows the player go back or not, if you say false to that and the player types "go back"
 /// The playr will then be given a reason that you specified.
 ///
-/// The room also contains a conversation. Converse struct is made of lines and choices, the choices are then
-/// made of a line and converse struct. this is then processed by calling the process_conversation of a Converse root.
+/// A room can have other descriptions that depend on the story so far. The first one whose
+/// conditions pass is used instead of the description, for example after the creek has been searched.
+/// Fragments are added to the end of the description, every one whose conditions pass, for
+/// example: "The gate stands open." The people, items, enemies and exits in the room are listed
+/// after it, so the description doesn't have to mention them, see: description.
+///
+/// Rooms can hold items that the player can take and drop. Dropped items stay in the room they
+/// were dropped in.
+///
+/// Rooms can have enemies in them, see Enemy. Hostile enemies attack as soon as the player
+/// walks in, the rest wait for the player to start the fight.
+///
+/// A room can be dark. Unless the player can see in the dark, for example with darkvision, they
+/// can't look around, explore or see what items are in it.
+///
+/// The room also contains the people in it. Each person has a conversation, which is a graph of nodes made of
+/// lines and choices, processed by calling process_conversation on it.
 /// Conversations allow the player to interact with the NPC in front of them.
 #[derive(Clone, Debug)]
 pub struct Room {
+    pub id: String,
     pub name: String,
     pub description: String,
     pub actions: HashMap<Action, Option<OnAction>>,
     pub exits: Vec<Exit>,
     pub go_back: GoBack,
     pub npcs: Option<Vec<Person>>,
+    pub items: Vec<Item>,
+    pub descriptions: Vec<ConditionalText>,
+    pub fragments: Vec<ConditionalText>,
+    pub dark: bool,
+    pub enemies: Vec<Enemy>,
+}
+
+/// Text that is only used when its conditions pass.
+#[derive(Clone, Debug)]
+pub struct ConditionalText {
+    pub conditions: Vec<Condition>,
+    pub text: String,
+}
+
+impl ConditionalText {
+
+    pub fn new(conditions: Vec<Condition>, text: String) -> Self {
+        ConditionalText {
+            conditions: conditions,
+            text: text,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -78,6 +175,7 @@ impl GoBack {
 
 impl Room {
     pub fn new(
+        id: String,
         name: String,
         description: String,
         actions: HashMap<Action, Option<OnAction>>,
@@ -86,87 +184,339 @@ impl Room {
         npcs: Option<Vec<Person>>) -> Self {
 
         Room {
+            id: id,
             name: name,
             description: description,
             actions: actions,
             exits: exits,
             go_back: go_back,
             npcs: npcs,
+            items: Vec::new(),
+            descriptions: Vec::new(),
+            fragments: Vec::new(),
+            dark: false,
+            enemies: Vec::new(),
         }
     }
 
-    pub fn describe(&self) -> &String {
-        return &self.description;
+    /// Set the descriptions that replace the description when their conditions pass.
+    pub fn with_descriptions(mut self, descriptions: Vec<ConditionalText>) -> Self {
+        self.descriptions = descriptions;
+
+        return self;
     }
 
-    pub fn name(&self) -> &String {
-        return &self.name;
+    /// Set the text added to the end of the description when its conditions pass.
+    pub fn with_fragments(mut self, fragments: Vec<ConditionalText>) -> Self {
+        self.fragments = fragments;
+
+        return self;
     }
 
-    pub fn exit(&self, direction: Direction) -> Option<Room> {
+    /// Make the room dark, or light.
+    pub fn dark(mut self, dark: bool) -> Self {
+        self.dark = dark;
 
-        for exit in &self.exits {
-            if exit.direction == direction {
+        return self;
+    }
+
+    /// Set the enemies that are in the room.
+    pub fn with_enemies(mut self, enemies: Vec<Enemy>) -> Self {
+        self.enemies = enemies;
+
+        return self;
+    }
 
-                let next_room = exit.room.clone();
+    /// Find the enemy in the room with the name, ignoring case.
+    pub fn find_enemy(&self, name: &str) -> Option<&Enemy> {
+        return self.enemies.iter().find(|enemy| enemy.matches(name));
+    }
+
+    /// Set the items that are in the room.
+    pub fn with_items(mut self, items: Vec<Item>) -> Self {
+        self.items = items;
+
+        return self;
+    }
 
-                // I feel confident that if we fail here, someone messed up.
-                // This would mean you have a room with a exit but no room for the player
-                // to go too. So we should allow the system to panic.
-                return Some(next_room.unwrap());
+    /// Describe the room based on the story so far.
+    pub fn describe(&self, context: &mut dyn Context) -> String {
+        for description in &self.descriptions {
+            if context.check_all(&description.conditions) {
+                return description.text.clone();
             }
         }
 
+        return self.description.clone();
+    }
 
-        return None;
+    /// Put together everything the player is told about the room: the description, the fragments
+    /// whose conditions pass and what they can see in the room.
+    ///
+    /// When the player can't see they aren't told about the people, items or enemies in the room.
+    pub fn description(&self, can_see: bool, context: &mut dyn Context) -> Description {
+        let mut fragments = Vec::new();
+
+        for fragment in &self.fragments {
+            if context.check_all(&fragment.conditions) {
+                fragments.push(fragment.text.clone());
+            }
+        }
+
+        let mut description = Description::new(self.describe(context))
+            .with_fragments(fragments)
+            .with_exits(self.visible_exits(context))
+            .dark(!can_see);
+
+        if can_see {
+            description = description
+                .with_people(self.npcs.iter().flatten().map(|person| person.name.clone()).collect())
+                .with_items(self.items.iter().map(|item| item.name.clone()).collect())
+                .with_enemies(self.enemies.iter().map(|enemy| enemy.name.clone()).collect());
+        }
+
+        return description;
+    }
+
+    pub fn name(&self) -> &String {
+        return &self.name;
+    }
+
+    /// Find an item in the room by name or alias.
+    pub fn find_item(&self, name: &str) -> Option<&Item> {
+        return find_item(&self.items, name).map(|index| &self.items[index]);
+    }
+
+    /// Take an item out of the room.
+    pub fn take_item(&mut self, name: &str) -> Option<Item> {
+        return find_item(&self.items, name).map(|index| self.items.remove(index));
     }
 
-    pub fn do_action(&self, action: Action, bonus: i32) {
-        let actions = self.actions.clone();
+    /// Put an item in the room.
+    pub fn drop_item(&mut self, item: Item) {
+        self.items.push(item);
+    }
 
-        for (room_action, on_action) in actions {
-            if room_action == action && on_action.is_some() {
+    /// Gets the exit in the given direction.
+    ///
+    /// The exit might be closed or gated, it is up to the game to check before
+    /// letting the player through.
+    pub fn exit(&self, direction: Direction) -> Option<&Exit> {
+        return self.exits.iter().find(|exit| exit.direction == direction);
+    }
+
+    /// Find the exit the player is talking about, by its direction, its name or the name of
+    /// its door, for example: north, ladder or gate.
+    pub fn find_exit(&self, word: &str) -> Option<&Exit> {
+        return self.exits.iter().find(|exit| exit.matches(word));
+    }
 
-                if on_action.clone().unwrap().dc_check.is_some() {
-                    let roll = rand::thread_rng().gen_range(1,20) + bonus;
+    /// The exits the player can see, as they are listed, for example: north and up (ladder).
+    ///
+    /// Hidden exits are left out until their conditions pass.
+    pub fn visible_exits(&self, context: &mut dyn Context) -> Vec<String> {
+        return self.exits.iter()
+            .filter(|exit| exit.room.is_some())
+            .filter(|exit| !exit.hidden || context.check_all(&exit.conditions))
+            .map(|exit| exit.label())
+            .collect();
+    }
 
-                    let dc_check = on_action.clone().unwrap().dc_check.unwrap();
+    /// Find the exit with a door the player is talking about.
+    ///
+    /// The player can name the door (gate, hatch ...) or the direction it is in. If the
+    /// player doesn't say and there is only one door in the room, that's the one.
+    pub fn find_door(&mut self, target: &str) -> Option<&mut Exit> {
+        let doors: Vec<usize> = (0..self.exits.len()).filter(|index| self.exits[*index].door.is_some()).collect();
 
-                    if roll > dc_check {
-                        println!("Upon your roll of a: {}", roll);
-                        on_action.clone().unwrap().do_action()
-                    } else {
-                        println!("Failed to do the action. You failed the DC check: {}. You can try again.", roll);
-                    }
-                }
+        if target == "" {
+            if doors.len() == 1 {
+                return Some(&mut self.exits[doors[0]]);
+            }
 
-                on_action.clone().unwrap().do_action();
+            return None;
+        }
 
-                return;
+        for index in doors {
+            if self.exits[index].matches(target) {
+                return Some(&mut self.exits[index]);
             }
         }
 
-        println!("Cannot do that action in this area.");
+        return None;
+    }
+
+    /// Does the room respond to the action?
+    pub fn has_action(&self, action: &Action) -> bool {
+        return self.actions.get(action).map_or(false, |on_action| on_action.is_some());
+    }
+
+    pub fn do_action(&self, action: Action, bonus: i32, context: &mut dyn Context) {
+        match self.actions.get(&action) {
+            Some(Some(on_action)) => {
+                on_action.perform(bonus, context);
+            },
+            _ => context.console().println("Cannot do that action in this area."),
+        }
     }
 }
 
 /// Handels the rooms exit.
 ///
-/// Because rooms are built in a top down fashion, we have take in the direction and the
-/// room, which is an Option. we will panic if you have a direction but no room for the player to exit
-/// out too. The only time room should be None is if there is no exit from this room.
+/// An exit is a direction and the id of the room it leads to, which is an Option. we will panic
+/// if you have a direction but no room for the player to exit out too. The only time room should
+/// be None is if there is no exit from this room.
+///
+/// Exits can be gated:
+///
+/// - door: the exit is shut by a door that might be closed or locked.
+/// - conditions: every condition must pass before the player can go through.
+/// - hidden: while the conditions fail the exit acts as if it isn't there at all.
+///
+/// When the player is stopped by the conditions they are told the failure message, much like
+/// the reason on GoBack. The effects are applied each time the player goes through the exit.
+///
+/// An exit can have a name, such as ladder or house, so the player can go through it by name:
+/// climb ladder or enter house. The name is shown with the direction when the exits are
+/// listed: up (ladder). An exit with a name doesn't need a direction, use Direction::NONE.
 ///
 /// Rooms do not need to define exits going backwards. We assume that you can always go back
 /// to the previous room.
 #[derive(Clone, Debug)]
 pub struct Exit {
     pub direction: Direction,
-    pub room: Option<Room>
+    pub room: Option<String>,
+    pub name: Option<String>,
+    pub door: Option<Door>,
+    pub conditions: Vec<Condition>,
+    pub hidden: bool,
+    pub failure: Option<String>,
+    pub effects: Vec<Effect>,
+}
+
+impl Exit {
+
+    /// Create a new, always open, exit.
+    pub fn new(direction: Direction, room: Option<String>) -> Self {
+        Exit {
+            direction: direction,
+            room: room,
+            name: None,
+            door: None,
+            conditions: Vec::new(),
+            hidden: false,
+            failure: None,
+            effects: Vec::new(),
+        }
+    }
+
+    /// Set the name the player can use for the exit, for example: ladder.
+    pub fn with_name(mut self, name: Option<String>) -> Self {
+        self.name = name;
+
+        return self;
+    }
+
+    /// Set the effects of going through the exit.
+    pub fn with_effects(mut self, effects: Vec<Effect>) -> Self {
+        self.effects = effects;
+
+        return self;
+    }
+
+    /// Put a door across the exit.
+    pub fn with_door(mut self, door: Door) -> Self {
+        self.door = Some(door);
+
+        return self;
+    }
+
+    /// Set the conditions that must pass to use the exit and what the player is told when they don't.
+    pub fn with_conditions(mut self, conditions: Vec<Condition>, failure: Option<String>) -> Self {
+        self.conditions = conditions;
+        self.failure = failure;
+
+        return self;
+    }
+
+    /// Hide the exit until its conditions pass.
+    pub fn hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+
+        return self;
+    }
+
+    /// Is the word the player typed this exit? It can be the direction, the name of the exit
+    /// or the name of its door, ignoring case.
+    pub fn matches(&self, word: &str) -> bool {
+        let word = word.to_lowercase();
+
+        if self.direction != Direction::NONE && Direction::from_word(&word) == Some(self.direction.clone()) {
+            return true;
+        }
+
+        return self.name.iter().chain(self.door.as_ref().map(|door| &door.name))
+            .any(|name| name.to_lowercase() == word);
+    }
+
+    /// What the player calls the exit: its direction, or its name when it isn't in a direction.
+    pub fn word(&self) -> String {
+        match (&self.direction, &self.name) {
+            (Direction::NONE, Some(name)) => name.to_lowercase(),
+            _ => self.direction.name().to_string(),
+        }
+    }
+
+    /// How the exit is shown when the exits are listed, for example: north, up (ladder) or house.
+    pub fn label(&self) -> String {
+        let name = self.name.as_ref().or(self.door.as_ref().map(|door| &door.name));
+
+        match (&self.direction, name) {
+            (Direction::NONE, Some(name)) => name.to_lowercase(),
+            (direction, Some(name)) => format!("{} ({})", direction.name(), name.to_lowercase()),
+            (direction, None) => direction.name().to_string(),
+        }
+    }
+}
+
+pub fn make_exit(direction: Direction, room: String) -> Exit {
+    Exit::new(direction, Some(room))
+}
+
+/// A door, gate or anything else that can be shut across an exit.
+///
+/// A closed door can be opened. A locked door must first be unlocked with the key (the name
+/// or alias of an item), forced open with a STR check against force_dc or have its lock picked
+/// with a DEX check against pick_dc. When there is no key, force_dc or pick_dc the door can't be
+/// opened that way.
+#[derive(Clone, Debug)]
+pub struct Door {
+    pub name: String,
+    pub closed: bool,
+    pub locked: bool,
+    pub key: Option<String>,
+    pub force_dc: Option<i32>,
+    pub pick_dc: Option<i32>,
 }
 
-pub fn make_exit(direction: Direction, room: Room) -> Exit {
-    Exit {
-        direction: direction,
-        room: Some(room),
+impl Door {
+
+    /// Create a new door.
+    pub fn new(name: String, closed: bool, locked: bool, key: Option<String>, force_dc: Option<i32>) -> Self {
+        Door {
+            name: name,
+            closed: closed,
+            locked: locked,
+            key: key,
+            force_dc: force_dc,
+            pick_dc: None,
+        }
+    }
+
+    /// Let the lock be picked with a DEX check against the DC.
+    pub fn with_pick_dc(mut self, pick_dc: Option<i32>) -> Self {
+        self.pick_dc = pick_dc;
+
+        return self;
     }
 }
@@ -0,0 +1,89 @@
+use std::fmt;
+use std::collections::HashMap;
+use serde::{Serialize, Deserialize};
+
+/// A value kept in the story state.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub enum Value {
+    Bool(bool),
+    Int(i32),
+    Str(String),
+}
+
+impl Value {
+
+    /// Is the value set to something?
+    ///
+    /// True, any number but 0 and any text that isn't empty count as set.
+    pub fn is_truthy(&self) -> bool {
+        match self {
+            Value::Bool(value) => *value,
+            Value::Int(value) => *value != 0,
+            Value::Str(value) => !value.is_empty(),
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Bool(value) => write!(f, "{}", value),
+            Value::Int(value) => write!(f, "{}", value),
+            Value::Str(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// The story so far.
+///
+/// Holds flags and variables by name so the adventure can remember what the player has
+/// done, for example that they talked to the Poet or searched the creek.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct State {
+    pub values: HashMap<String, Value>,
+}
+
+impl State {
+
+    /// Create an empty state.
+    pub fn new() -> Self {
+        State {
+            values: HashMap::new(),
+        }
+    }
+
+    /// Get a value.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        return self.values.get(key);
+    }
+
+    /// Set a value.
+    pub fn set(&mut self, key: &str, value: Value) {
+        self.values.insert(key.to_string(), value);
+    }
+
+    /// Is the flag set?
+    ///
+    /// Values that have never been set are not.
+    pub fn flag(&self, key: &str) -> bool {
+        match self.get(key) {
+            Some(value) => value.is_truthy(),
+            None => false,
+        }
+    }
+
+    /// Get a number, values that have never been set or are not numbers are 0.
+    pub fn int(&self, key: &str) -> i32 {
+        match self.get(key) {
+            Some(Value::Int(value)) => *value,
+            _ => 0,
+        }
+    }
+
+    /// Add to a number.
+    pub fn add(&mut self, key: &str, amount: i32) {
+        let value = self.int(key) + amount;
+
+        self.set(key, Value::Int(value));
+    }
+}
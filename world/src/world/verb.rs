@@ -0,0 +1,73 @@
+use std::vec::Vec;
+use core::stats::Stat;
+use crate::world::actions::OnAction;
+
+/// A command an adventure adds to the game, for example: pray.
+///
+/// - name: what the player types, aliases are other words for it.
+/// - usage and help: how the command is listed when the player types help.
+/// - stat: what the player rolls with when an action for the command has a dc_check.
+/// - on_action: what happens when no room or person responds to the command.
+///
+/// Rooms and people respond to the command with an action of the same name, the same way
+/// they respond to look or steal. When the player names a person, that person responds.
+#[derive(Clone, Debug)]
+pub struct Verb {
+    pub name: String,
+    pub aliases: Vec<String>,
+    pub usage: String,
+    pub help: String,
+    pub stat: Stat,
+    pub on_action: Option<OnAction>,
+}
+
+impl Verb {
+
+    /// Create a new command that is rolled with int and does nothing until a room or person
+    /// responds to it.
+    pub fn new(name: String, help: String) -> Self {
+        Verb {
+            usage: name.clone(),
+            name: name,
+            aliases: Vec::new(),
+            help: help,
+            stat: Stat::Int,
+            on_action: None,
+        }
+    }
+
+    /// Set the other words the player can type for the command.
+    pub fn with_aliases(mut self, aliases: Vec<String>) -> Self {
+        self.aliases = aliases;
+
+        return self;
+    }
+
+    /// Set how the command is shown in help, for example: pray (to PERSON).
+    pub fn with_usage(mut self, usage: String) -> Self {
+        self.usage = usage;
+
+        return self;
+    }
+
+    /// Set the stat the command is rolled with.
+    pub fn with_stat(mut self, stat: Stat) -> Self {
+        self.stat = stat;
+
+        return self;
+    }
+
+    /// Set what happens when no room or person responds to the command.
+    pub fn with_on_action(mut self, on_action: Option<OnAction>) -> Self {
+        self.on_action = on_action;
+
+        return self;
+    }
+
+    /// Is the word the player typed this command?
+    pub fn matches(&self, word: &str) -> bool {
+        let word = word.to_lowercase();
+
+        return self.name.to_lowercase() == word || self.aliases.iter().any(|alias| alias.to_lowercase() == word);
+    }
+}